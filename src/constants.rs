@@ -20,3 +20,32 @@ pub const MAX_INIT_ROWS_COUNT: u8 = 6;
 
 pub const MOVE_DOWN_AFTER_KEY: &str = "move_down_after";
 pub const DEFAULT_MOVE_DOWN_AFTER: u8 = 5;
+
+pub const QUEUE_DEPTH_KEY: &str = "queue_depth";
+pub const MIN_QUEUE_DEPTH: u8 = 1;
+pub const DEFAULT_QUEUE_DEPTH: u8 = 3;
+pub const MAX_QUEUE_DEPTH: u8 = 5;
+
+pub const GRID_MODE_KEY: &str = "grid_mode";
+
+pub const FIRE_MODE_KEY: &str = "fire_mode";
+
+pub const POP_HINT_KEY: &str = "pop_hint";
+
+pub const LEVEL_STARS_KEY: &str = "level_stars";
+
+pub const SEED_KEY: &str = "seed";
+pub const LAST_SEED_KEY: &str = "last_seed";
+pub const SEED_ARG: &str = "--seed";
+pub const HEADLESS_ARG: &str = "--headless";
+pub const SHOTS_ARG: &str = "--shots";
+pub const GRID_MODE_ARG: &str = "--grid-mode";
+pub const FIRE_MODE_ARG: &str = "--fire-mode";
+pub const LEVEL_ARG: &str = "--level";
+
+pub const LEVEL_PATHS: [&str; 4] = [
+    "levels/first_steps.level.ron",
+    "levels/window.level.ron",
+    "levels/rainbow.level.ron",
+    "levels/treasure.level.ron",
+];
@@ -2,6 +2,7 @@ use bevy::prelude::{Commands, Input, KeyCode, NextState, Res, ResMut};
 
 use crate::{
     components::AppState,
+    gameplay::rng::resources::GameRng,
     loading::font_assets::FontAssets,
     ui::{
         components::{NextStateButton, NoneComponent},
@@ -10,7 +11,7 @@ use crate::{
             button_utils::{append_large_text_button, append_middle_text_button},
             camera_utils::build_ui_camera,
             menu_utils::build_menu,
-            text_utils::append_large_text,
+            text_utils::{append_large_text, append_middle_text},
         },
     },
 };
@@ -20,6 +21,7 @@ pub fn setup_menu(
     font_assets: Res<FontAssets>,
     button_colors: Res<UIMenuButtonColors>,
     text_colors: Res<UIMenuTextColors>,
+    game_rng: Res<GameRng>,
 ) {
     build_ui_camera(&mut commands);
     build_menu(&mut commands, |parent| {
@@ -30,6 +32,13 @@ pub fn setup_menu(
             &text_colors,
             None::<NoneComponent>,
         );
+        append_middle_text(
+            parent,
+            format!("Сид: {}", game_rng.seed).as_str(),
+            &font_assets,
+            &text_colors,
+            None::<NoneComponent>,
+        );
         append_large_text_button(
             parent,
             Some(NextStateButton {
@@ -2,7 +2,8 @@ use bevy::prelude::{Commands, Input, KeyCode, NextState, Res, ResMut};
 
 use crate::{
     components::AppState,
-    loading::font_assets::FontAssets,
+    gameplay::level::resources::{LevelResult, Objectives},
+    loading::{font_assets::FontAssets, level_assets::LevelAssets},
     ui::{
         components::{NextStateButton, NoneComponent},
         resources::{ColorType, UIMenuButtonColors, UIMenuTextColors},
@@ -10,7 +11,7 @@ use crate::{
             button_utils::{append_large_text_button, append_middle_text_button},
             camera_utils::build_ui_camera,
             menu_utils::build_menu,
-            text_utils::append_large_text,
+            text_utils::{append_large_text, append_middle_text},
         },
     },
 };
@@ -20,6 +21,9 @@ pub fn setup_menu(
     font_assets: Res<FontAssets>,
     button_colors: Res<UIMenuButtonColors>,
     text_colors: Res<UIMenuTextColors>,
+    some_level_result: Option<Res<LevelResult>>,
+    level_assets: Res<LevelAssets>,
+    objectives: Res<Objectives>,
 ) {
     build_ui_camera(&mut commands);
     build_menu(&mut commands, |parent| {
@@ -30,6 +34,38 @@ pub fn setup_menu(
             &text_colors,
             None::<NoneComponent>,
         );
+        for met_objective in objectives.objective.met_objectives(&objectives.progress) {
+            append_middle_text(
+                parent,
+                format!("Выполнено: {}", met_objective).as_str(),
+                &font_assets,
+                &text_colors,
+                None::<NoneComponent>,
+            );
+        }
+        if let Some(level_result) = some_level_result {
+            append_middle_text(
+                parent,
+                format!(
+                    "Уровень {} пройден, звёзды: {} из 3",
+                    level_result.level + 1,
+                    level_result.stars
+                )
+                .as_str(),
+                &font_assets,
+                &text_colors,
+                None::<NoneComponent>,
+            );
+            if level_result.level + 1 == level_assets.levels.len() {
+                append_middle_text(
+                    parent,
+                    "Кампания пройдена",
+                    &font_assets,
+                    &text_colors,
+                    None::<NoneComponent>,
+                );
+            }
+        }
         append_large_text_button(
             parent,
             Some(NextStateButton {
@@ -1,15 +1,20 @@
 use bevy::{
-    prelude::{shape, Assets, Bundle, Mesh, Quat, Res, ResMut, Transform, Vec2, Vec3, Visibility},
-    sprite::MaterialMesh2dBundle,
+    prelude::{
+        shape, Assets, Bundle, Color, Mesh, Quat, Res, ResMut, Transform, Vec2, Vec3, Visibility,
+    },
+    sprite::{ColorMaterial, MaterialMesh2dBundle},
 };
 
 use crate::gameplay::{
-    constants::{AIM_LINE_Z_INDEX, AIM_TARGET_Z_INDEX, BALL_DIAMETER, INNER_RADIUS_COEFF},
+    constants::{
+        AIM_GHOST_Z_INDEX, AIM_LINE_Z_INDEX, AIM_TARGET_Z_INDEX, BALL_DIAMETER, INNER_RADIUS_COEFF,
+    },
     grid::resources::Grid,
     materials::resources::GameplayMaterials,
+    meshes::resources::GameplayMeshes,
 };
 
-use super::{components::AimLine, components::AimTarget};
+use super::components::{AimGhost, AimLine, AimTarget};
 
 pub struct AimBundle;
 
@@ -60,4 +65,21 @@ impl AimBundle {
             AimLine,
         )
     }
+
+    pub fn new_ghost(
+        gameplay_meshes: &Res<GameplayMeshes>,
+        materials: &mut ResMut<Assets<ColorMaterial>>,
+    ) -> impl Bundle {
+        (
+            MaterialMesh2dBundle {
+                mesh: gameplay_meshes.grid_ball.clone().into(),
+                // own material, its color follows projectile species
+                material: materials.add(Color::NONE.into()),
+                transform: Transform::from_translation(Vec3::new(0.0, 0.0, AIM_GHOST_Z_INDEX)),
+                visibility: Visibility::Hidden,
+                ..Default::default()
+            },
+            AimGhost::default(),
+        )
+    }
 }
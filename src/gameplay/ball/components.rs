@@ -7,27 +7,55 @@ use bevy::{
     utils::HashSet,
 };
 
+use fastrand::Rng;
+use serde::Deserialize;
+
 use crate::gameplay::constants::MAX_APPEAR_TIME;
 
-#[derive(Component)]
-pub struct ProjectileBall {
-    pub is_flying: bool,
-    pub snap_vel: Vec2,
-    pub is_snapped: bool,
+/// projectile lifecycle, transitions send events from gameplay/events.rs
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProjectileState {
+    /// waits at the bottom for the shot
+    #[default]
+    Loaded,
+    /// pointer is pressed and aim is drawn
+    Aiming,
+    /// shot and bounces off side walls, sends ShotFired and ProjectileBounced
+    Flying,
+    /// touched a grid ball, waits for the settle detector
+    Settling,
+    /// became a grid ball, sends ProjectileSettled
+    Snapped,
+    /// removed in a cluster before it snapped, sends ProjectilePopped
+    Popped,
 }
 
-impl Default for ProjectileBall {
-    fn default() -> Self {
-        Self {
-            is_flying: false,
-            snap_vel: Vec2::ZERO,
-            is_snapped: false,
-        }
+impl ProjectileState {
+    pub fn is_loaded(&self) -> bool {
+        matches!(self, ProjectileState::Loaded | ProjectileState::Aiming)
     }
+
+    pub fn is_flying(&self) -> bool {
+        matches!(self, ProjectileState::Flying | ProjectileState::Settling)
+    }
+}
+
+#[derive(Component, Default)]
+pub struct ProjectileBall {
+    pub state: ProjectileState,
+    /// velocity direction on the first touch with a grid ball
+    pub snap_vel: Vec2,
 }
 
+/// shot fireball, a sensor which burns grid balls on its way
 #[derive(Component)]
-pub struct NextProjectileBall {}
+pub struct Fireball;
+
+#[derive(Component)]
+pub struct NextProjectileBall {
+    /// slot in projectile queue, 0 is loaded next
+    pub index: usize,
+}
 
 #[derive(Component)]
 pub struct GridBall {
@@ -45,6 +73,36 @@ impl Default for GridBall {
 #[derive(Component)]
 pub struct MagneticGridBall {}
 
+/// ice-encased grid ball, skipped by clusters until adjacent pops crack all layers
+#[derive(Component)]
+pub struct Frozen {
+    pub layers: u8,
+}
+
+/// ice drawn over frozen ball
+#[derive(Component)]
+pub struct IceOverlay;
+
+/// grid ball takes the next active colour every turn
+#[derive(Component)]
+pub struct Chameleon;
+
+/// small rainbow dot in the middle of chameleon ball
+#[derive(Component)]
+pub struct ChameleonMark;
+
+/// grid ball holds collectible, it is freed when the ball is popped or dropped
+#[derive(Component)]
+pub struct Collectible;
+
+/// collectible drawn over its ball
+#[derive(Component)]
+pub struct CollectibleMark;
+
+/// grid ball from the top row of generated grid
+#[derive(Component)]
+pub struct TopRowBall;
+
 #[derive(Component)]
 pub struct GridBallScaleAnimate {
     pub scale: Vec2,
@@ -52,10 +110,10 @@ pub struct GridBallScaleAnimate {
 }
 
 impl GridBallScaleAnimate {
-    pub fn from_scale(scale: Vec2) -> Self {
+    pub fn from_scale(scale: Vec2, rng: &mut Rng) -> Self {
         Self {
             scale,
-            timer: Timer::from_seconds(fastrand::f32() * MAX_APPEAR_TIME, TimerMode::Once),
+            timer: Timer::from_seconds(rng.f32() * MAX_APPEAR_TIME, TimerMode::Once),
         }
     }
 }
@@ -91,7 +149,15 @@ impl OutBall {
     }
 }
 
-#[derive(Component, PartialEq, Clone, Copy, Eq, Hash)]
+/// out ball removed by bomb blast or fireball burn instead of colour cluster
+#[derive(Component)]
+pub struct BlastOutBall;
+
+/// rainbow out ball counts as the colour of its cluster
+#[derive(Component)]
+pub struct MatchedOutBall(pub Species);
+
+#[derive(Component, PartialEq, Clone, Copy, Eq, Hash, Deserialize)]
 pub enum Species {
     Red,
     Blue,
@@ -100,6 +166,14 @@ pub enum Species {
     Yellow,
     White,
     Purple,
+    /// special projectile, clears balls around the impact regardless of colour
+    Bomb,
+    /// wildcard, joins the largest neighbour colour group and matches any colour in grid
+    Rainbow,
+    /// passes through grid balls and burns them until it hits the top wall
+    Fireball,
+    /// indestructible blocker, never in a cluster, leaves grid only by falling
+    Stone,
 }
 
 impl Display for Species {
@@ -115,6 +189,10 @@ impl Display for Species {
                 Species::Yellow => "Species::Yellow",
                 Species::White => "Species::White",
                 Species::Purple => "Species::Purple",
+                Species::Bomb => "Species::Bomb",
+                Species::Rainbow => "Species::Rainbow",
+                Species::Fireball => "Species::Fireball",
+                Species::Stone => "Species::Stone",
             },
         )
     }
@@ -133,6 +211,10 @@ impl Debug for Species {
                 Species::Yellow => "Species::Yellow",
                 Species::White => "Species::White",
                 Species::Purple => "Species::Purple",
+                Species::Bomb => "Species::Bomb",
+                Species::Rainbow => "Species::Rainbow",
+                Species::Fireball => "Species::Fireball",
+                Species::Stone => "Species::Stone",
             },
         )
     }
@@ -148,6 +230,10 @@ impl Into<Color> for Species {
             Species::Yellow => Color::YELLOW,
             Species::White => Color::WHITE,
             Species::Purple => Color::PURPLE,
+            Species::Bomb => Color::GRAY,
+            Species::Rainbow => Color::WHITE,
+            Species::Fireball => Color::ORANGE_RED,
+            Species::Stone => Color::DARK_GRAY,
         }
     }
 }
@@ -175,17 +261,76 @@ impl From<u8> for Species {
 }
 
 impl Species {
-    pub fn random_species(total_colors: u8) -> Species {
-        Self::from(fastrand::u8(1..=total_colors))
+    /// special projectiles and stones are not grid colours, they are never replaced by active ones
+    pub fn is_special(&self) -> bool {
+        matches!(
+            self,
+            Species::Bomb | Species::Rainbow | Species::Fireball | Species::Stone
+        )
     }
 
-    pub fn pick_random(active_species: &HashSet<Species>, total_colors: u8) -> Species {
-        if active_species.len() > 0 {
-            let colors_in_grid: Vec<&Species> = active_species.into_iter().collect();
-            let i = fastrand::usize(..colors_in_grid.len());
+    /// ball joins cluster of given colour, wildcard joins any, stone none
+    pub fn matches(&self, cluster_species: &Species) -> bool {
+        match self {
+            Species::Stone => false,
+            Species::Rainbow => true,
+            _ => self == cluster_species,
+        }
+    }
+
+    /// plural adjective for HUD
+    pub fn title(&self) -> &'static str {
+        match self {
+            Species::Red => "красные",
+            Species::Blue => "синие",
+            Species::Blau => "голубые",
+            Species::Green => "зелёные",
+            Species::Yellow => "жёлтые",
+            Species::White => "белые",
+            Species::Purple => "фиолетовые",
+            Species::Bomb => "взрывные",
+            Species::Rainbow => "радужные",
+            Species::Fireball => "огненные",
+            Species::Stone => "каменные",
+        }
+    }
+
+    /// next colour in grid after this one, wraps around to the first
+    pub fn next_active(&self, active_species: &HashSet<Species>) -> Option<Species> {
+        let mut colors_in_grid: Vec<Species> = active_species
+            .iter()
+            .filter(|species| !species.is_special())
+            .copied()
+            .collect();
+        colors_in_grid.sort_by_key(|species| *species as u8);
+        colors_in_grid
+            .iter()
+            .find(|species| **species as u8 > *self as u8)
+            .or(colors_in_grid.first())
+            .copied()
+    }
+
+    pub fn random_species(total_colors: u8, rng: &mut Rng) -> Species {
+        Self::from(rng.u8(1..=total_colors))
+    }
+
+    pub fn pick_random(
+        active_species: &HashSet<Species>,
+        total_colors: u8,
+        rng: &mut Rng,
+    ) -> Species {
+        // stones and special projectiles are never handed out as projectile colour
+        let mut colors_in_grid: Vec<&Species> = active_species
+            .into_iter()
+            .filter(|species| !species.is_special())
+            .collect();
+        if !colors_in_grid.is_empty() {
+            // hash set order is not stable, keep picks reproducible for the same seed
+            colors_in_grid.sort_by_key(|species| **species as u8);
+            let i = rng.usize(..colors_in_grid.len());
             *colors_in_grid[i]
         } else {
-            Species::random_species(total_colors)
+            Species::random_species(total_colors, rng)
         }
     }
 }
@@ -213,3 +358,9 @@ impl Default for AimTarget {
 
 #[derive(Component)]
 pub struct AimLine;
+
+/// translucent projectile at predicted resting position
+#[derive(Component, Default)]
+pub struct AimGhost {
+    pub species: Option<Species>,
+}
@@ -1,4 +1,5 @@
 use bevy::{
+    ecs::system::SystemParam,
     prelude::{default, BuildChildren, Bundle, Commands, Entity, Res, Transform, Vec2, Vec3},
     sprite::MaterialMesh2dBundle,
 };
@@ -8,17 +9,31 @@ use bevy_xpbd_2d::prelude::{
 };
 
 use crate::gameplay::{
-    constants::{BALL_RADIUS, GRID_BALL_Z_INDEX},
-    grid::utils::build_ball_text,
+    constants::{
+        BALL_RADIUS, CHAMELEON_MARK_Z_INDEX, COLLECTIBLE_Z_INDEX, GRID_BALL_Z_INDEX, ICE_Z_INDEX,
+    },
+    grid::{
+        components::{ProjectileSweep, SettleDetector},
+        utils::build_ball_text,
+    },
     materials::resources::GameplayMaterials,
     meshes::resources::GameplayMeshes,
     physics::layers::Layer,
+    rng::resources::GameRng,
 };
 
 use super::components::{
-    GridBall, GridBallScaleAnimate, MagneticGridBall, ProjectileBall, Species,
+    Chameleon, ChameleonMark, Collectible, CollectibleMark, Frozen, GridBall, GridBallScaleAnimate,
+    IceOverlay, MagneticGridBall, ProjectileBall, Species,
 };
 
+/// meshes and materials which grid balls are spawned with
+#[derive(SystemParam)]
+pub struct GridBallAssets<'w> {
+    pub gameplay_meshes: Res<'w, GameplayMeshes>,
+    pub gameplay_materials: Res<'w, GameplayMaterials>,
+}
+
 pub struct GridBallBundle;
 
 impl GridBallBundle {
@@ -39,6 +54,13 @@ impl GridBallBundle {
             GridBall::default(),
             MagneticGridBall {},
             species,
+            Self::physics(pos),
+        )
+    }
+
+    /// collision components without rigid body, shared with the aim simulation
+    pub fn physics(pos: Vec2) -> impl Bundle {
+        (
             Collider::ball(BALL_RADIUS),
             ColliderMassProperties::ZERO,
             MassPropertiesBundle::new_computed(&Collider::ball(1.0), 1.0),
@@ -49,10 +71,96 @@ impl GridBallBundle {
             },
             Friction::ZERO,
             AngularDamping(0.9),
-            CollisionLayers::new([Layer::Grid], [Layer::Walls, Layer::Grid]),
+            CollisionLayers::new([Layer::Grid], [Layer::Walls, Layer::Grid, Layer::Fireball]),
         )
     }
 
+    /// fireball senses walls and grid balls, aim simulation passes only walls
+    pub fn fireball_layers(is_aim: bool) -> CollisionLayers {
+        match is_aim {
+            true => CollisionLayers::new([Layer::Fireball], [Layer::Walls]),
+            false => CollisionLayers::new([Layer::Fireball], [Layer::Walls, Layer::Grid]),
+        }
+    }
+
+    /// ball keeps its species, ice overlay is a child
+    pub fn freeze(
+        commands: &mut Commands,
+        entity: Entity,
+        layers: u8,
+        gameplay_meshes: &Res<GameplayMeshes>,
+        gameplay_materials: &Res<GameplayMaterials>,
+    ) {
+        commands
+            .entity(entity)
+            .insert(Frozen { layers })
+            .with_children(|parent| {
+                parent.spawn((
+                    MaterialMesh2dBundle {
+                        mesh: gameplay_meshes.grid_ball.clone().into(),
+                        material: gameplay_materials.ice_for_layers(layers),
+                        transform: Transform::from_translation(Vec3::new(0.0, 0.0, ICE_Z_INDEX)),
+                        ..default()
+                    },
+                    IceOverlay,
+                ));
+            });
+    }
+
+    /// chameleon is marked by small rainbow dot
+    pub fn camouflage(
+        commands: &mut Commands,
+        entity: Entity,
+        gameplay_meshes: &Res<GameplayMeshes>,
+        gameplay_materials: &Res<GameplayMaterials>,
+    ) {
+        commands
+            .entity(entity)
+            .insert(Chameleon)
+            .with_children(|parent| {
+                parent.spawn((
+                    MaterialMesh2dBundle {
+                        mesh: gameplay_meshes.next_projectile_ball.clone().into(),
+                        material: gameplay_materials.rainbow.clone(),
+                        transform: Transform::from_translation(Vec3::new(
+                            0.0,
+                            0.0,
+                            CHAMELEON_MARK_Z_INDEX,
+                        )),
+                        ..default()
+                    },
+                    ChameleonMark,
+                ));
+            });
+    }
+
+    /// collectible is golden dot drawn over the ball and its ice
+    pub fn trap_collectible(
+        commands: &mut Commands,
+        entity: Entity,
+        gameplay_meshes: &Res<GameplayMeshes>,
+        gameplay_materials: &Res<GameplayMaterials>,
+    ) {
+        commands
+            .entity(entity)
+            .insert(Collectible)
+            .with_children(|parent| {
+                parent.spawn((
+                    MaterialMesh2dBundle {
+                        mesh: gameplay_meshes.next_projectile_ball.clone().into(),
+                        material: gameplay_materials.collectible.clone(),
+                        transform: Transform::from_translation(Vec3::new(
+                            0.0,
+                            0.0,
+                            COLLECTIBLE_Z_INDEX,
+                        )),
+                        ..default()
+                    },
+                    CollectibleMark,
+                ));
+            });
+    }
+
     pub fn spawn(
         commands: &mut Commands,
         gameplay_meshes: &Res<GameplayMeshes>,
@@ -64,6 +172,7 @@ impl GridBallBundle {
         some_species: Option<Species>,
         is_appear_animation: bool,
         debug_text: bool,
+        game_rng: &mut GameRng,
     ) -> (Entity, Species) {
         let mut transform = Transform::from_translation(position.extend(GRID_BALL_Z_INDEX));
         if is_appear_animation {
@@ -71,7 +180,7 @@ impl GridBallBundle {
         }
         let species = match some_species {
             Some(species) => species,
-            None => Species::random_species(total_colors),
+            None => Species::random_species(total_colors, &mut game_rng.rng),
         };
 
         let mut entity_commands = commands.spawn(Self::new(
@@ -88,10 +197,17 @@ impl GridBallBundle {
             entity_commands.insert(RigidBody::Dynamic);
         }
         if is_appear_animation {
-            entity_commands.insert(GridBallScaleAnimate::from_scale(Vec2::ONE));
+            entity_commands.insert(GridBallScaleAnimate::from_scale(
+                Vec2::ONE,
+                &mut game_rng.rng,
+            ));
         }
         if is_projectile {
-            entity_commands.insert(ProjectileBall::default());
+            entity_commands.insert((
+                ProjectileBall::default(),
+                SettleDetector::default(),
+                ProjectileSweep::default(),
+            ));
         }
         if debug_text {
             entity_commands.with_children(|parent| {
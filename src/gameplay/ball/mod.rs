@@ -5,15 +5,20 @@ use bevy::prelude::{
 use crate::components::AppState;
 
 use self::{
-    resources::ProjectileHelper,
+    resources::{AimSimulation, PopHint, ProjectileHelper},
     systems::{
-        aim_systems::{cleanup_aim_lines, cleanup_aim_target, draw_aim, setup_aim_target},
+        aim_systems::{
+            cleanup_aim_ghost, cleanup_aim_lines, cleanup_aim_target, draw_aim, draw_aim_ghost,
+            highlight_pop_hint, setup_aim_target, setup_pop_hint, tick_aim_simulation,
+        },
         out_ball_systems::{animate_out_ball, check_out_ball_for_delete},
         projectile_systems::{
             check_projectile_species, cleanup_next_projectile_ball, cleanup_projectile_ball,
-            projectile_reload, shoot_projectile,
+            ignite_fireball, play_shot_audio, projectile_reload, setup_projectile_queue,
+            shoot_projectile, tick_projectile_reload, use_bomb_booster,
         },
         scale_systems::animate_grid_ball_scale,
+        swap_systems::{detect_swap_projectile, swap_projectile},
     },
 };
 
@@ -22,23 +27,38 @@ pub mod components;
 pub mod grid_ball_bundle;
 pub mod out_ball_bundle;
 pub mod projectile_ball_bundle;
-mod resources;
+pub mod resources;
 mod systems;
 mod utils;
 pub struct ProjectilePlugin;
 
 impl Plugin for ProjectilePlugin {
     fn build(&self, app: &mut App) {
-        app.init_resource::<ProjectileHelper>()
+        app.insert_non_send_resource(AimSimulation::default())
+            .init_resource::<ProjectileHelper>()
+            .init_resource::<PopHint>()
+            .add_systems(
+                OnEnter(AppState::GameplayInit),
+                (setup_projectile_queue, setup_pop_hint),
+            )
             .add_systems(OnEnter(AppState::Gameplay), setup_aim_target)
             .add_systems(
                 Update,
                 (
+                    tick_projectile_reload.before(projectile_reload),
                     projectile_reload.after(apply_deferred),
                     shoot_projectile,
+                    play_shot_audio.after(shoot_projectile),
+                    ignite_fireball.after(shoot_projectile),
+                    use_bomb_booster,
+                    detect_swap_projectile.before(shoot_projectile),
+                    swap_projectile.after(detect_swap_projectile),
                     animate_out_ball,
                     check_out_ball_for_delete,
+                    tick_aim_simulation.before(draw_aim),
                     draw_aim,
+                    draw_aim_ghost.after(draw_aim),
+                    highlight_pop_hint.after(draw_aim_ghost),
                     animate_grid_ball_scale,
                 )
                     .run_if(in_state(AppState::Gameplay)),
@@ -52,6 +72,7 @@ impl Plugin for ProjectilePlugin {
                 (
                     cleanup_projectile_ball,
                     cleanup_aim_target,
+                    cleanup_aim_ghost,
                     cleanup_aim_lines,
                     cleanup_next_projectile_ball,
                 ),
@@ -4,7 +4,11 @@ use bevy::{
 };
 
 use crate::gameplay::{
-    constants::NEXT_PROJECTILE_Z_INDEX, materials::resources::GameplayMaterials,
+    constants::{
+        NEXT_PROJECTILE_SPAWN_SIDE, NEXT_PROJECTILE_TRAY_GAP, NEXT_PROJECTILE_TRAY_SCALE_STEP,
+        NEXT_PROJECTILE_Z_INDEX,
+    },
+    materials::resources::GameplayMaterials,
     meshes::resources::GameplayMeshes,
 };
 
@@ -14,7 +18,8 @@ pub struct NextProjectileBallBundle;
 
 impl NextProjectileBallBundle {
     pub fn new(
-        pos: Vec2,
+        index: usize,
+        bottom: f32,
         species: Species,
         gameplay_meshes: &Res<GameplayMeshes>,
         gameplay_materials: &Res<GameplayMaterials>,
@@ -24,14 +29,20 @@ impl NextProjectileBallBundle {
                 mesh: gameplay_meshes.next_projectile_ball.clone().into(),
                 material: gameplay_materials.from_species(species),
                 transform: Transform::from_translation(Vec3::new(
-                    pos.x,
-                    pos.y,
+                    NEXT_PROJECTILE_SPAWN_SIDE + index as f32 * NEXT_PROJECTILE_TRAY_GAP,
+                    bottom,
                     NEXT_PROJECTILE_Z_INDEX,
-                )),
+                ))
+                .with_scale(Self::slot_scale(index).extend(1.0)),
                 ..default()
             },
-            NextProjectileBall {},
+            NextProjectileBall { index },
             species,
         )
     }
+
+    /// queue tray goes to one side and shrinks with distance from projectile
+    pub fn slot_scale(index: usize) -> Vec2 {
+        Vec2::splat(1.0 - index as f32 * NEXT_PROJECTILE_TRAY_SCALE_STEP)
+    }
 }
@@ -1,23 +1,118 @@
+use std::{
+    collections::VecDeque,
+    fmt::{Display, Formatter, Result},
+};
+
 use bevy::{
-    prelude::Resource,
+    ecs::event::ManualEventReader,
+    hierarchy::HierarchyPlugin,
+    prelude::{App, Entity, MinimalPlugins, Resource, TransformPlugin, Vec2},
     time::{Timer, TimerMode},
+    utils::HashMap,
+};
+use bevy_xpbd_2d::{
+    prelude::{CollisionStarted, PhysicsPlugins},
+    resources::{Gravity, PhysicsTimestep},
 };
 
-use crate::gameplay::constants::PROJECTILE_RELOAD_TIME;
+use crate::{
+    constants::DEFAULT_QUEUE_DEPTH,
+    gameplay::constants::{
+        AIM_SIMULATION_COOLDOWN_TIME, AIM_SIMULATION_TIMESTEP, BOMB_BOOSTERS,
+        PROJECTILE_RELOAD_TIME,
+    },
+};
 
 use super::components::Species;
 
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FireMode {
+    /// next projectile is loaded after the previous one snapped
+    #[default]
+    Single,
+    /// next projectile is loaded by reload timer while previous ones still fly
+    Rapid,
+}
+
+impl Display for FireMode {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        match self {
+            FireMode::Single => write!(f, "single"),
+            FireMode::Rapid => write!(f, "rapid"),
+        }
+    }
+}
+
+impl From<&str> for FireMode {
+    fn from(value: &str) -> Self {
+        match value {
+            "rapid" => FireMode::Rapid,
+            _ => FireMode::Single,
+        }
+    }
+}
+
 #[derive(Resource)]
 pub struct ProjectileHelper {
+    pub fire_mode: FireMode,
+    /// rapid fire cadence, finished when the next projectile can be loaded
     pub reload_timer: Timer,
-    pub buffer: Vec<Species>,
+    /// upcoming projectiles, the front one is loaded next
+    pub queue: VecDeque<Species>,
+    pub queue_depth: u8,
+    /// bombs left which player can put in front of the queue
+    pub bomb_boosters: u8,
 }
 
 impl Default for ProjectileHelper {
     fn default() -> Self {
         Self {
-            reload_timer: Timer::from_seconds(PROJECTILE_RELOAD_TIME, TimerMode::Repeating),
-            buffer: Vec::default(),
+            fire_mode: FireMode::default(),
+            reload_timer: Timer::from_seconds(PROJECTILE_RELOAD_TIME, TimerMode::Once),
+            queue: VecDeque::default(),
+            queue_depth: DEFAULT_QUEUE_DEPTH,
+            bomb_boosters: BOMB_BOOSTERS,
+        }
+    }
+}
+
+/// easy mode aid, highlights balls which the aimed shot would pop
+#[derive(Resource, Default)]
+pub struct PopHint {
+    pub enabled: bool,
+    pub popped: Vec<Entity>,
+    pub marks: Vec<Entity>,
+}
+
+/// separate physics world, the aimed shot is stepped through it to predict its path,
+/// walls and grid balls stay there between shots and only follow changes of the real ones
+pub struct AimSimulation {
+    pub app: App,
+    pub collision_reader: ManualEventReader<CollisionStarted>,
+    /// real wall or grid ball entity to its static copy in the simulation world
+    pub mirrored: HashMap<Entity, Entity>,
+    pub cooldown_timer: Timer,
+}
+
+impl Default for AimSimulation {
+    fn default() -> Self {
+        let mut app = App::new();
+        app.add_plugins((
+            MinimalPlugins,
+            TransformPlugin,
+            HierarchyPlugin,
+            PhysicsPlugins::default(),
+        ))
+        .insert_resource(Gravity(Vec2::ZERO))
+        // one physics step per update regardless of the real frame time
+        .insert_resource(PhysicsTimestep::FixedOnce(AIM_SIMULATION_TIMESTEP));
+        app.finish();
+        app.cleanup();
+        Self {
+            app,
+            collision_reader: ManualEventReader::default(),
+            mirrored: HashMap::default(),
+            cooldown_timer: Timer::from_seconds(AIM_SIMULATION_COOLDOWN_TIME, TimerMode::Once),
         }
     }
 }
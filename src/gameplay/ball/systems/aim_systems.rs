@@ -1,45 +1,84 @@
-use std::ops::Add;
-
 use bevy::{
+    ecs::system::SystemParam,
     prelude::{
-        warn, Assets, Commands, DespawnRecursiveExt, Entity, Mesh, Query, Res, ResMut, Transform,
-        Vec2, Visibility, With, Without,
+        default, warn, Assets, BuildChildren, Color, ColorMaterial, Commands, DespawnRecursiveExt,
+        Entity, Events, Handle, Mesh, NonSendMut, Query, Res, ResMut, Time, Transform, Vec2, Vec3,
+        Visibility, With, Without,
     },
+    sprite::MaterialMesh2dBundle,
     utils::HashSet,
 };
-use bevy_xpbd_2d::prelude::{Collider, ShapeHitData, SpatialQuery, SpatialQueryFilter};
+use bevy_pkv::PkvStore;
+use bevy_xpbd_2d::{
+    prelude::{CollisionStarted, LinearVelocity, Position, RigidBody},
+    PhysicsSchedule,
+};
 
-use crate::gameplay::{
-    ball::{
-        aim_bundle::AimBundle,
-        components::{AimLine, AimTarget, GridBall, ProjectileBall},
-        utils::cleanup_aim_line_utils,
+use crate::{
+    constants::POP_HINT_KEY,
+    gameplay::{
+        ball::{
+            aim_bundle::AimBundle,
+            components::{AimGhost, AimLine, AimTarget, GridBall, ProjectileBall, Species},
+            grid_ball_bundle::GridBallBundle,
+            resources::{AimSimulation, PopHint},
+            utils::{cleanup_aim_line_utils, sync_aim_mirror},
+        },
+        constants::{
+            AIM_GHOST_ALPHA, AIM_SIMULATION_MAX_STEPS, POP_HINT_Z_INDEX, PROJECTILE_SPEED,
+        },
+        grid::{
+            resources::Grid,
+            utils::{find_anchors, predict_popped, predict_rest_position},
+        },
+        lines::components::LineType,
+        materials::resources::GameplayMaterials,
+        meshes::resources::GameplayMeshes,
+        walls::{
+            components::{TopWall, WallType},
+            wall_bundle::WallBundle,
+        },
     },
-    constants::{
-        BALL_RADIUS, CAST_RAY_BOUNCE_Y_ADD, CAST_RAY_MAX_TOI, CAST_RAY_TRIES, CAST_RAY_VELOCITY,
-        CAST_RAY_VELOCITY_TOLERANCE,
-    },
-    grid::resources::Grid,
-    lines::components::LineType,
-    materials::resources::GameplayMaterials,
-    physics::layers::Layer,
-    walls::components::WallType,
+    settings_menu::utils::pop_hint_utils::read_pop_hint,
 };
 
+pub fn tick_aim_simulation(mut aim_simulation: NonSendMut<AimSimulation>, time: Res<Time>) {
+    aim_simulation.cooldown_timer.tick(time.delta());
+}
+
+/// walls and grid balls mirrored into the aim simulation world
+#[derive(SystemParam)]
+pub struct AimPlayfield<'w, 's> {
+    wall_query: Query<'w, 's, (Entity, &'static Position, &'static WallType)>,
+    balls_query: Query<
+        'w,
+        's,
+        (
+            Entity,
+            &'static Position,
+            &'static Species,
+            Option<&'static ProjectileBall>,
+        ),
+        With<GridBall>,
+    >,
+}
+
 pub fn draw_aim(
     mut commands: Commands,
     mut meshes: ResMut<Assets<Mesh>>,
     gameplay_materials: Res<GameplayMaterials>,
-    wall_query: Query<(Entity, &WallType), With<WallType>>,
-    balls_query: Query<Entity, With<GridBall>>,
-    spatial_query: SpatialQuery,
+    mut aim_simulation: NonSendMut<AimSimulation>,
+    aim_playfield: AimPlayfield,
     aim_line_query: Query<Entity, With<AimLine>>,
     mut aim_target_query: Query<
         (&mut AimTarget, &mut Transform, &mut Visibility),
-        (With<AimTarget>, Without<LineType>),
+        Without<LineType>,
     >,
-    projectile_ball_query: Query<Entity, With<ProjectileBall>>,
 ) {
+    let AimPlayfield {
+        wall_query,
+        balls_query,
+    } = aim_playfield;
     if let Ok((mut aim_target, mut target_transform, mut target_visibility)) =
         aim_target_query.get_single_mut()
     {
@@ -49,89 +88,134 @@ pub fn draw_aim(
             aim_target.draw_vel = Vec2::ZERO;
         }
 
-        if aim_target.pointer_pressed && aim_target.draw_vel != aim_target.aim_vel {
+        // redraw only if pointer position (draw velocity in this case) changed,
+        // fast pointer moves are simulated once per cooldown
+        if aim_target.pointer_pressed
+            && aim_target.draw_vel != aim_target.aim_vel
+            && aim_simulation.cooldown_timer.finished()
+        {
             aim_target.draw_vel = aim_target.aim_vel;
-            // redraw only if pointer position (draw velocity in this case) changed
+            aim_simulation.cooldown_timer.reset();
 
             cleanup_aim_line_utils(&mut commands, &aim_line_query);
-            let mut exclude_entities: HashSet<Entity> = HashSet::default();
-            for projectile_entity in projectile_ball_query.iter() {
-                exclude_entities.insert(projectile_entity);
+            *target_visibility = Visibility::Hidden;
+
+            // sync playfield into the simulation world, unchanged bodies stay as they are
+            let AimSimulation {
+                app,
+                collision_reader,
+                mirrored,
+                ..
+            } = &mut *aim_simulation;
+            let world = &mut app.world;
+            let mut synced: HashSet<Entity> = HashSet::with_capacity(mirrored.len());
+            for (entity, position, wall_type) in wall_query.iter() {
+                sync_aim_mirror(world, mirrored, entity, position.0, |world| {
+                    let mut wall = world.spawn(WallBundle::physics(position.0, wall_type));
+                    if wall_type.is_top() {
+                        wall.insert(TopWall);
+                    }
+                    wall.id()
+                });
+                synced.insert(entity);
             }
-            let spatial_query_filter = SpatialQueryFilter::default()
-                .with_masks([Layer::Walls, Layer::Grid])
-                .without_entities(exclude_entities);
-
-            let shape = Collider::ball(BALL_RADIUS);
-            let mut ray_start = aim_target.aim_pos;
-            let mut ray_vel = aim_target.aim_vel.normalize() * CAST_RAY_VELOCITY;
-            let mut count = 0;
-
-            loop {
-                count = count + 1;
-                if count > CAST_RAY_TRIES {
-                    let message = format!("Break aim iteration draw, reached max iteration count aim.aim_pos({:?}) aim_vel({:?})", aim_target.aim_pos, aim_target.aim_vel);
-                    warn!(message);
-                    break;
+            for (entity, position, _, _) in balls_query
+                .iter()
+                .filter(|(_, _, _, some_projectile)| some_projectile.is_none())
+            {
+                sync_aim_mirror(world, mirrored, entity, position.0, |world| {
+                    world
+                        .spawn((
+                            GridBallBundle::physics(position.0),
+                            RigidBody::Static,
+                            GridBall::default(),
+                        ))
+                        .id()
+                });
+                synced.insert(entity);
+            }
+            mirrored.retain(|entity, mirror| {
+                let is_synced = synced.contains(entity);
+                if !is_synced {
+                    world.despawn(*mirror);
                 }
-                if let Some(ShapeHitData {
-                    entity,
-                    time_of_impact: _,
-                    point1,
-                    point2,
-                    normal1: _,
-                    normal2: _,
-                }) = spatial_query.cast_shape(
-                    &shape,
-                    ray_start,
-                    0.0,
-                    ray_vel,
-                    CAST_RAY_MAX_TOI,
-                    true,
-                    spatial_query_filter.clone(),
-                ) {
-                    if let Ok((_, wall_type)) = wall_query.get(entity) {
-                        let mut center = point1 - point2;
-                        if wall_type.is_side() {
-                            center = center.add(Vec2::new(
-                                0.0,
-                                CAST_RAY_BOUNCE_Y_ADD * ray_vel.normalize().y,
-                            ));
-                        } else {
-                            target_transform.translation =
-                                center.extend(target_transform.translation.z);
-                            *target_visibility = Visibility::Visible;
-                        }
-                        commands.spawn(AimBundle::new_line(
-                            ray_start,
-                            center,
-                            &mut meshes,
-                            &gameplay_materials,
-                        ));
-                        if wall_type.is_top() {
-                            break;
-                        }
-                        ray_start = center;
-                        let mut reverse_x_vel = -ray_vel.x;
-                        match reverse_x_vel.signum() > 0.0 {
-                            true => reverse_x_vel += CAST_RAY_VELOCITY_TOLERANCE,
-                            false => reverse_x_vel -= CAST_RAY_VELOCITY_TOLERANCE,
+                is_synced
+            });
+
+            // fireball passes through grid, its aim ignores grid layer
+            let is_fireball = balls_query.iter().any(|(_, _, species, some_projectile)| {
+                *species == Species::Fireball
+                    && some_projectile.is_some_and(|projectile| projectile.state.is_loaded())
+            });
+            let mut projectile = world.spawn((
+                GridBallBundle::physics(aim_target.aim_pos),
+                RigidBody::Dynamic,
+                LinearVelocity(aim_target.aim_vel.normalize() * PROJECTILE_SPEED),
+            ));
+            if is_fireball {
+                projectile.insert(GridBallBundle::fireball_layers(true));
+            }
+            let projectile = projectile.id();
+            collision_reader.clear(world.resource::<Events<CollisionStarted>>());
+
+            let mut path = vec![aim_target.aim_pos];
+            let mut prev_vel = aim_target.aim_vel;
+            for step in 0..AIM_SIMULATION_MAX_STEPS {
+                match step {
+                    // full update prepares spawned and moved bodies before the first step
+                    0 => app.update(),
+                    _ => app.world.run_schedule(PhysicsSchedule),
+                }
+                let world = &app.world;
+                let (Some(position), Some(linear_velocity)) = (
+                    world.get::<Position>(projectile),
+                    world.get::<LinearVelocity>(projectile),
+                ) else {
+                    break;
+                };
+                // real shot snaps on collision start with a grid ball or the top wall
+                let touched = collision_reader
+                    .iter(world.resource::<Events<CollisionStarted>>())
+                    .filter_map(|CollisionStarted(entity1, entity2)| {
+                        match (*entity1 == projectile, *entity2 == projectile) {
+                            (true, _) => Some(*entity2),
+                            (_, true) => Some(*entity1),
+                            _ => None,
                         }
-                        ray_vel = Vec2::new(reverse_x_vel, ray_vel.y);
-                    } else if let Ok(_) = balls_query.get(entity) {
-                        let center = point1 - point2;
-                        target_transform.translation =
-                            center.extend(target_transform.translation.z);
-                        commands.spawn(AimBundle::new_line(
-                            ray_start,
-                            center,
-                            &mut meshes,
-                            &gameplay_materials,
-                        ));
-                        *target_visibility = Visibility::Visible;
-                        break;
-                    }
+                    })
+                    .any(|other| {
+                        world.get::<GridBall>(other).is_some()
+                            || world.get::<TopWall>(other).is_some()
+                    });
+                if touched {
+                    path.push(position.0);
+                    target_transform.translation =
+                        position.0.extend(target_transform.translation.z);
+                    *target_visibility = Visibility::Visible;
+                    break;
+                }
+                // side wall bounce flips horizontal velocity
+                if linear_velocity.x.signum() != prev_vel.x.signum() {
+                    path.push(position.0);
                 }
+                prev_vel = linear_velocity.0;
+            }
+            app.world.despawn(projectile);
+            if *target_visibility == Visibility::Hidden {
+                let message = format!(
+                    "Break aim simulation, reached max step count aim.aim_pos({:?}) aim_vel({:?})",
+                    aim_target.aim_pos, aim_target.aim_vel
+                );
+                warn!(message);
+            }
+
+            for segment in path.windows(2) {
+                commands.spawn(AimBundle::new_line(
+                    segment[0],
+                    segment[1],
+                    &mut meshes,
+                    &gameplay_materials,
+                ));
             }
         }
     }
@@ -140,7 +224,9 @@ pub fn draw_aim(
 pub fn setup_aim_target(
     mut commands: Commands,
     mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
     gameplay_materials: Res<GameplayMaterials>,
+    gameplay_meshes: Res<GameplayMeshes>,
     grid: Res<Grid>,
 ) {
     commands.spawn(AimBundle::new_target(
@@ -149,6 +235,66 @@ pub fn setup_aim_target(
         &gameplay_materials,
         &grid,
     ));
+    commands.spawn(AimBundle::new_ghost(&gameplay_meshes, &mut materials));
+}
+
+/// ghost ball shows where the loaded projectile is going to rest
+pub fn draw_aim_ghost(
+    aim_target_query: Query<(&AimTarget, &Transform, &Visibility), Without<AimGhost>>,
+    mut ghost_query: Query<(
+        &mut AimGhost,
+        &mut Transform,
+        &mut Visibility,
+        &Handle<ColorMaterial>,
+    )>,
+    projectile_query: Query<(&ProjectileBall, &Species)>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    grid: Res<Grid>,
+) {
+    let (mut aim_ghost, mut ghost_transform, mut ghost_visibility, ghost_material) =
+        match ghost_query.get_single_mut() {
+            Ok(ghost) => ghost,
+            Err(_) => return,
+        };
+    let (aim_target, target_transform, target_visibility) = match aim_target_query.get_single() {
+        Ok(aim_target) => aim_target,
+        Err(_) => return,
+    };
+    let loaded_species = projectile_query
+        .iter()
+        .find(|(projectile_ball, _)| projectile_ball.state.is_loaded())
+        .map(|(_, species)| *species);
+
+    // fireball never rests in grid
+    let species = match loaded_species {
+        Some(species)
+            if species != Species::Fireball
+                && aim_target.pointer_pressed
+                && *target_visibility != Visibility::Hidden =>
+        {
+            species
+        }
+        _ => {
+            aim_ghost.species = None;
+            *ghost_visibility = Visibility::Hidden;
+            return;
+        }
+    };
+    aim_ghost.species = Some(species);
+
+    let rest_position = predict_rest_position(&grid, target_transform.translation.truncate());
+    ghost_transform.translation = rest_position.extend(ghost_transform.translation.z);
+    let species_color: Color = species.into();
+    let ghost_color = species_color.with_a(AIM_GHOST_ALPHA);
+    // touch material only when projectile species changed
+    if let Some(material) = materials.get(ghost_material) {
+        if material.color != ghost_color {
+            if let Some(material) = materials.get_mut(ghost_material) {
+                material.color = ghost_color;
+            }
+        }
+    }
+    *ghost_visibility = Visibility::Visible;
 }
 
 pub fn cleanup_aim_target(
@@ -160,6 +306,87 @@ pub fn cleanup_aim_target(
     }
 }
 
+pub fn setup_pop_hint(mut pop_hint: ResMut<PopHint>, pkv: Res<PkvStore>) {
+    pop_hint.enabled = read_pop_hint(POP_HINT_KEY, &pkv);
+    pop_hint.popped.clear();
+    pop_hint.marks.clear();
+}
+
+/// marks balls which the ghost ball would pop together with floating ones
+pub fn highlight_pop_hint(
+    mut commands: Commands,
+    ghost_query: Query<(&AimGhost, &Transform, &Visibility)>,
+    balls_query: Query<&RigidBody, With<GridBall>>,
+    grid: Res<Grid>,
+    mut pop_hint: ResMut<PopHint>,
+    gameplay_meshes: Res<GameplayMeshes>,
+    gameplay_materials: Res<GameplayMaterials>,
+) {
+    if !pop_hint.enabled {
+        return;
+    }
+    let mut popped: Vec<Entity> = match ghost_query.get_single() {
+        Ok((
+            AimGhost {
+                species: Some(species),
+            },
+            ghost_transform,
+            ghost_visibility,
+        )) if *ghost_visibility != Visibility::Hidden => {
+            let anchors = find_anchors(&grid, |entity| match balls_query.get(entity) {
+                Ok(rigid_body) => rigid_body.is_kinematic(),
+                Err(_) => false,
+            });
+            predict_popped(
+                &grid,
+                &anchors,
+                ghost_transform.translation.truncate(),
+                *species,
+            )
+            .into_iter()
+            .collect()
+        }
+        _ => vec![],
+    };
+    popped.sort();
+    if popped == pop_hint.popped {
+        return;
+    }
+
+    for mark_entity in pop_hint.marks.drain(..) {
+        // mark could be removed together with popped ball already
+        if let Some(entity_commands) = commands.get_entity(mark_entity) {
+            entity_commands.despawn_recursive();
+        }
+    }
+    for ball_entity in popped.iter() {
+        if let Some(mut entity_commands) = commands.get_entity(*ball_entity) {
+            entity_commands.with_children(|parent| {
+                let mark_entity = parent
+                    .spawn(MaterialMesh2dBundle {
+                        mesh: gameplay_meshes.next_projectile_ball.clone().into(),
+                        material: gameplay_materials.pop_hint.clone(),
+                        transform: Transform::from_translation(Vec3::new(
+                            0.0,
+                            0.0,
+                            POP_HINT_Z_INDEX,
+                        )),
+                        ..default()
+                    })
+                    .id();
+                pop_hint.marks.push(mark_entity);
+            });
+        }
+    }
+    pop_hint.popped = popped;
+}
+
+pub fn cleanup_aim_ghost(mut commands: Commands, ghost_query: Query<Entity, With<AimGhost>>) {
+    for ghost_entity in ghost_query.iter() {
+        commands.entity(ghost_entity).despawn_recursive();
+    }
+}
+
 pub fn cleanup_aim_lines(mut commands: Commands, aim_line_query: Query<Entity, With<AimLine>>) {
     cleanup_aim_line_utils(&mut commands, &aim_line_query);
 }
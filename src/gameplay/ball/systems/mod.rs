@@ -2,3 +2,4 @@ pub mod aim_systems;
 pub mod out_ball_systems;
 pub mod projectile_systems;
 pub mod scale_systems;
+pub mod swap_systems;
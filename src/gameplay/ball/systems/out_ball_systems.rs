@@ -7,6 +7,7 @@ use bevy_xpbd_2d::prelude::{ExternalForce, LinearVelocity};
 use crate::gameplay::{
     ball::components::{OutBall, OutBallAnimation},
     constants::OUT_BALL_GRAVITY,
+    rng::resources::GameRng,
 };
 
 pub fn animate_out_ball(
@@ -21,6 +22,7 @@ pub fn animate_out_ball(
         ),
         With<OutBall>,
     >,
+    mut game_rng: ResMut<GameRng>,
 ) {
     for (
         mut grid_ball_out,
@@ -34,14 +36,14 @@ pub fn animate_out_ball(
             grid_ball_out.started = true;
             ball_transform.translation.z = 2.0; // slightly on top of grid
             if grid_ball_out.animation_type == OutBallAnimation::FloatingCluster {
-                linear_velocity.0 = Vec2::new(0.0, fastrand::i32(-200..=0) as f32);
+                linear_velocity.0 = Vec2::new(0.0, game_rng.rng.i32(-200..=0) as f32);
             } else {
                 linear_velocity.0 = Vec2::new(
-                    match fastrand::bool() {
-                        true => fastrand::i32(-200..=-100) as f32,
-                        false => fastrand::i32(100..=200) as f32,
+                    match game_rng.rng.bool() {
+                        true => game_rng.rng.i32(-200..=-100) as f32,
+                        false => game_rng.rng.i32(100..=200) as f32,
                     },
-                    fastrand::i32(100..=200) as f32,
+                    game_rng.rng.i32(100..=200) as f32,
                 );
             }
             external_force.set_force(Vec2::new(0.0, -OUT_BALL_GRAVITY));
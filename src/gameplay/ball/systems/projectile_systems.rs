@@ -1,41 +1,74 @@
 use bevy::{
+    ecs::system::SystemParam,
     prelude::{
-        Assets, Camera, ColorMaterial, Commands, DespawnRecursiveExt, Entity, EventReader,
-        GlobalTransform, Handle, Input, MouseButton, Query, Res, ResMut, Touches, Vec2, With,
-        Without,
+        Camera, Commands, DespawnRecursiveExt, Entity, EventReader, EventWriter, GlobalTransform,
+        Input, KeyCode, MouseButton, Query, Res, ResMut, Touches, Vec2, With, Without,
     },
+    time::Time,
     window::{PrimaryWindow, Window},
 };
 use bevy_pkv::PkvStore;
-use bevy_xpbd_2d::prelude::{LinearVelocity, Position};
+use bevy_xpbd_2d::prelude::{LinearVelocity, Position, Sensor};
 
 use crate::{
+    constants::{FIRE_MODE_KEY, QUEUE_DEPTH_KEY},
     game_audio::utils::pkv_play_shoot_audio,
     gameplay::{
         ball::{
             components::{
-                AimLine, AimTarget, GridBall, NextProjectileBall, ProjectileBall, Species,
+                AimLine, AimTarget, Fireball, GridBall, NextProjectileBall, ProjectileBall,
+                ProjectileState, Species,
             },
-            grid_ball_bundle::GridBallBundle,
+            grid_ball_bundle::{GridBallAssets, GridBallBundle},
             projectile_ball_bundle::NextProjectileBallBundle,
-            resources::ProjectileHelper,
-            utils::cleanup_next_projectile_ball_utils,
+            resources::{FireMode, ProjectileHelper},
+            utils::{cleanup_next_projectile_ball_utils, pick_queue_species},
         },
         constants::{
-            NEXT_PROJECTILE_SPAWN_BOTTOM, NEXT_PROJECTILE_SPAWN_SIDE, PROJECTILE_SHOOT_BOTTOM,
-            PROJECTILE_SPAWN_BOTTOM, PROJECTILE_SPEED,
+            BOMB_BOOSTERS, BOMB_BOOSTER_KEYCODE, NEXT_PROJECTILE_SPAWN_BOTTOM,
+            PROJECTILE_SHOOT_BOTTOM, PROJECTILE_SPAWN_BOTTOM, PROJECTILE_SPEED,
+        },
+        events::{ProjectileReload, ScriptedShot, ShotFired},
+        grid::{
+            components::{ProjectileSweep, SettleDetector},
+            resources::Grid,
         },
-        events::ProjectileReload,
-        grid::resources::Grid,
         main_camera::components::MainCamera,
         materials::resources::GameplayMaterials,
-        meshes::resources::GameplayMeshes,
+        playfield::resources::Playfield,
+        rng::resources::GameRng,
         utils::detect_pointer_position,
     },
     loading::audio_assets::AudioAssets,
+    settings_menu::utils::{fire_mode_utils::read_fire_mode, queue_depth_utils::read_queue_depth},
     ui::resources::PointerCooldown,
 };
 
+pub fn setup_projectile_queue(mut projectile_helper: ResMut<ProjectileHelper>, pkv: Res<PkvStore>) {
+    projectile_helper.queue.clear();
+    projectile_helper.queue_depth = read_queue_depth(QUEUE_DEPTH_KEY, &pkv);
+    projectile_helper.fire_mode = read_fire_mode(FIRE_MODE_KEY, &pkv);
+    projectile_helper.bomb_boosters = BOMB_BOOSTERS;
+    // first projectile is loaded right away
+    let reload_duration = projectile_helper.reload_timer.duration();
+    projectile_helper.reload_timer.reset();
+    projectile_helper.reload_timer.tick(reload_duration);
+}
+
+pub fn tick_projectile_reload(
+    mut projectile_helper: ResMut<ProjectileHelper>,
+    time: Res<Time>,
+    mut projectile_reload_writer: EventWriter<ProjectileReload>,
+) {
+    if projectile_helper.fire_mode != FireMode::Rapid || projectile_helper.reload_timer.finished() {
+        return;
+    }
+    projectile_helper.reload_timer.tick(time.delta());
+    if projectile_helper.reload_timer.just_finished() {
+        projectile_reload_writer.send(ProjectileReload);
+    }
+}
+
 pub fn cleanup_projectile_ball(
     mut commands: Commands,
     projectile_query: Query<Entity, With<ProjectileBall>>,
@@ -55,17 +88,17 @@ pub fn cleanup_next_projectile_ball(
 pub fn projectile_reload(
     mut commands: Commands,
     grid: Res<Grid>,
-    gameplay_meshes: Res<GameplayMeshes>,
-    gameplay_materials: Res<GameplayMaterials>,
+    grid_ball_assets: GridBallAssets,
     mut projectile_helper: ResMut<ProjectileHelper>,
     mut projectile_reload_events: EventReader<ProjectileReload>,
     grid_balls_query: Query<Entity, (With<GridBall>, Without<ProjectileBall>)>,
-    window_query: Query<&Window, With<PrimaryWindow>>,
+    playfield: Res<Playfield>,
     next_projectile_query: Query<Entity, With<NextProjectileBall>>,
-    projectile_query: Query<Entity, With<ProjectileBall>>,
+    projectile_query: Query<(Entity, &ProjectileBall)>,
+    mut game_rng: ResMut<GameRng>,
 ) {
     if grid_balls_query.iter().len() == 0 {
-        for projectile_entity in projectile_query.iter() {
+        for (projectile_entity, _) in projectile_query.iter() {
             commands.entity(projectile_entity).despawn_recursive();
         }
         return; // no more balls in grid
@@ -75,23 +108,35 @@ pub fn projectile_reload(
         return;
     }
     projectile_reload_events.clear();
+    let GridBallAssets {
+        gameplay_meshes,
+        gameplay_materials,
+    } = grid_ball_assets;
 
-    let species = match projectile_helper.buffer.pop() {
+    if projectile_helper.fire_mode == FireMode::Rapid {
+        // snapped or popped projectiles do not load the next one, reload timer does
+        let is_loaded = projectile_query
+            .iter()
+            .any(|(_, projectile_ball)| projectile_ball.state.is_loaded());
+        if is_loaded || !projectile_helper.reload_timer.finished() {
+            return;
+        }
+    }
+
+    let species = match projectile_helper.queue.pop_front() {
         Some(species) => {
-            // if picked from buffer color is absent in grid
+            // if picked from queue color is absent in grid
             // generate the new one
-            if let Some(_) = grid.active_species.get(&species) {
+            if species.is_special() || grid.active_species.contains(&species) {
                 species
             } else {
-                Species::pick_random(&grid.active_species, grid.total_colors)
+                grid.pick_species(&mut game_rng.rng)
             }
         }
-        None => Species::pick_random(&grid.active_species, grid.total_colors),
+        None => grid.pick_species(&mut game_rng.rng),
     };
 
-    let window = window_query.single();
-    let projectile_spawn_bottom =
-        -(window.height() - PROJECTILE_SPAWN_BOTTOM - window.height() / 2.0);
+    let projectile_spawn_bottom = playfield.bottom_y(PROJECTILE_SPAWN_BOTTOM);
 
     let (entity, _) = GridBallBundle::spawn(
         &mut commands,
@@ -104,31 +149,25 @@ pub fn projectile_reload(
         Some(species),
         true,
         true,
+        &mut game_rng,
     );
     println!(
         "=>>>>> ProjectileReload spawn {:?} {} y {}",
         entity, species, projectile_spawn_bottom
     );
 
-    projectile_helper.buffer.push(Species::pick_random(
-        &grid.active_species,
-        grid.total_colors,
-    ));
+    while projectile_helper.queue.len() < projectile_helper.queue_depth as usize {
+        projectile_helper
+            .queue
+            .push_back(pick_queue_species(&grid, &mut game_rng.rng));
+    }
 
     cleanup_next_projectile_ball_utils(&mut commands, &next_projectile_query);
-    if let Some(species) = projectile_helper.buffer.last() {
-        let next_projectile_spawn_bottom: f32 =
-            -(window.height() - NEXT_PROJECTILE_SPAWN_BOTTOM - window.height() / 2.0);
-
+    let next_projectile_spawn_bottom = playfield.bottom_y(NEXT_PROJECTILE_SPAWN_BOTTOM);
+    for (index, species) in projectile_helper.queue.iter().enumerate() {
         commands.spawn(NextProjectileBallBundle::new(
-            Vec2::new(-NEXT_PROJECTILE_SPAWN_SIDE, next_projectile_spawn_bottom),
-            *species,
-            &gameplay_meshes,
-            &gameplay_materials,
-        ));
-
-        commands.spawn(NextProjectileBallBundle::new(
-            Vec2::new(NEXT_PROJECTILE_SPAWN_SIDE, next_projectile_spawn_bottom),
+            index,
+            next_projectile_spawn_bottom,
             *species,
             &gameplay_meshes,
             &gameplay_materials,
@@ -137,54 +176,107 @@ pub fn projectile_reload(
 }
 
 pub fn check_projectile_species(
+    mut commands: Commands,
     mut projectile_query: Query<
-        (&ProjectileBall, &mut Species, &Handle<ColorMaterial>),
-        With<ProjectileBall>,
+        (Entity, &ProjectileBall, &mut Species),
+        Without<NextProjectileBall>,
     >,
+    mut next_projectile_query: Query<(Entity, &NextProjectileBall, &mut Species)>,
     grid: Res<Grid>,
-    mut materials: ResMut<Assets<ColorMaterial>>,
+    gameplay_materials: Res<GameplayMaterials>,
+    mut projectile_helper: ResMut<ProjectileHelper>,
+    mut game_rng: ResMut<GameRng>,
 ) {
-    for (projectile_ball, mut projectile_species, handle_projectile_material) in
-        projectile_query.iter_mut()
+    if grid.active_species.is_empty() {
+        return;
+    }
+    // replace material handles, species materials are shared with grid balls
+    for (projectile_entity, projectile_ball, mut projectile_species) in projectile_query.iter_mut()
     {
-        if !projectile_ball.is_flying && grid.active_species.len() > 0 {
-            if let None = grid.active_species.get(projectile_species.as_ref()) {
-                if let Some(projectile_material) = materials.get_mut(&handle_projectile_material) {
-                    let new_species = Species::pick_random(&grid.active_species, grid.total_colors);
-                    println!(
-                        "Change projectile color from {} into {}",
-                        projectile_species.as_ref(),
-                        new_species
-                    );
-                    *projectile_species = new_species;
-                    // change material
-                    projectile_material.color = new_species.into();
-                }
+        if projectile_ball.state.is_loaded()
+            && !projectile_species.is_special()
+            && !grid.active_species.contains(projectile_species.as_ref())
+        {
+            let new_species = grid.pick_species(&mut game_rng.rng);
+            println!(
+                "Change projectile color from {} into {}",
+                projectile_species.as_ref(),
+                new_species
+            );
+            *projectile_species = new_species;
+            commands
+                .entity(projectile_entity)
+                .insert(gameplay_materials.from_species(new_species));
+        }
+    }
+
+    for species in projectile_helper.queue.iter_mut() {
+        if !species.is_special() && !grid.active_species.contains(species) {
+            *species = grid.pick_species(&mut game_rng.rng);
+        }
+    }
+    for (next_entity, next_projectile, mut next_species) in next_projectile_query.iter_mut() {
+        if let Some(species) = projectile_helper.queue.get(next_projectile.index) {
+            if *species != *next_species {
+                println!(
+                    "Change queue slot {} color from {} into {}",
+                    next_projectile.index,
+                    next_species.as_ref(),
+                    species
+                );
+                *next_species = *species;
+                commands
+                    .entity(next_entity)
+                    .insert(gameplay_materials.from_species(*species));
             }
         }
     }
 }
 
+/// pointer of mouse or touch, headless script shoots without it
+#[derive(SystemParam)]
+pub struct ShotInput<'w, 's> {
+    window_query: Query<'w, 's, &'static Window, With<PrimaryWindow>>,
+    camera_query: Query<'w, 's, (&'static Camera, &'static GlobalTransform), With<MainCamera>>,
+    mouse_button_input: Res<'w, Input<MouseButton>>,
+    touches: Res<'w, Touches>,
+    scripted_shot_events: EventReader<'w, 's, ScriptedShot>,
+}
+
+impl ShotInput<'_, '_> {
+    /// pointer position, pressed, released and aquired flags
+    fn detect(&mut self) -> (Vec2, bool, bool, bool) {
+        let pointer = detect_pointer_position(
+            &self.window_query,
+            &self.camera_query,
+            &self.mouse_button_input,
+            &self.touches,
+        );
+        // scripted shot acts like pointer released at target position
+        match self.scripted_shot_events.iter().last() {
+            Some(scripted_shot) => (scripted_shot.target, false, true, true),
+            None => pointer,
+        }
+    }
+}
+
 pub fn shoot_projectile(
-    mut commands: Commands,
-    window_query: Query<&Window, With<PrimaryWindow>>,
-    camera_query: Query<(&Camera, &GlobalTransform), With<MainCamera>>,
-    mouse_button_input: Res<Input<MouseButton>>,
-    touches: Res<Touches>,
+    mut shot_input: ShotInput,
     mut projectile_ball_query: Query<
         (Entity, &Position, &mut LinearVelocity, &mut ProjectileBall),
         (With<ProjectileBall>, Without<AimTarget>, Without<AimLine>),
     >,
-    audio_assets: Res<AudioAssets>,
     pointer_cooldown: Res<PointerCooldown>,
-    pkv: Res<PkvStore>,
     mut aim_target_query: Query<&mut AimTarget, With<AimTarget>>,
+    playfield: Res<Playfield>,
+    mut projectile_helper: ResMut<ProjectileHelper>,
+    mut writer_shot_fired: EventWriter<ShotFired>,
 ) {
     if pointer_cooldown.started {
         return;
     }
     let (pointer_position, pointer_pressed, pointer_released, pointer_aquired) =
-        detect_pointer_position(&window_query, &camera_query, &mouse_button_input, &touches);
+        shot_input.detect();
 
     if !(pointer_pressed || pointer_released) {
         return;
@@ -193,12 +285,10 @@ pub fn shoot_projectile(
     for (projectile_entity, projectile_position, mut linear_velocity, mut projectile_ball) in
         projectile_ball_query.iter_mut()
     {
-        if projectile_ball.is_flying {
+        if !projectile_ball.state.is_loaded() {
             continue;
         }
-        let window = window_query.single();
-        let projectile_shoot_bottom =
-            -(window.height() - PROJECTILE_SHOOT_BOTTOM - window.height() / 2.0);
+        let projectile_shoot_bottom = playfield.bottom_y(PROJECTILE_SHOOT_BOTTOM);
 
         let mut target_position = pointer_position;
         if target_position.y < projectile_shoot_bottom {
@@ -209,16 +299,18 @@ pub fn shoot_projectile(
             aim.pointer_pressed = pointer_pressed;
             aim.pointer_released = pointer_released;
             if pointer_pressed {
-                if projectile_ball.is_flying {
-                    aim.pointer_pressed = false
-                } else {
-                    aim.aim_pos = projectile_position.0;
-                    aim.aim_vel = target_position - aim.aim_pos;
-                }
+                aim.aim_pos = projectile_position.0;
+                aim.aim_vel = target_position - aim.aim_pos;
             }
         }
+        if pointer_pressed {
+            projectile_ball.state = ProjectileState::Aiming;
+        }
 
-        if pointer_aquired && !projectile_ball.is_flying && pointer_released {
+        if pointer_released && !pointer_aquired {
+            projectile_ball.state = ProjectileState::Loaded;
+        }
+        if pointer_aquired && pointer_released {
             let aim_direction = target_position - projectile_position.0;
             linear_velocity.0 = aim_direction.normalize() * PROJECTILE_SPEED;
 
@@ -226,9 +318,67 @@ pub fn shoot_projectile(
                 "SHOOOOOT {:?} linear_velocity {} position {}",
                 projectile_entity, linear_velocity.0, projectile_position.0
             );
-            projectile_ball.is_flying = true;
+            projectile_ball.state = ProjectileState::Flying;
+            if projectile_helper.fire_mode == FireMode::Rapid {
+                projectile_helper.reload_timer.reset();
+            }
+            writer_shot_fired.send(ShotFired {
+                projectile_entity,
+                velocity: linear_velocity.0,
+            });
+        }
+    }
+}
+
+/// booster puts a bomb in front of the queue, tray is updated by species check
+pub fn use_bomb_booster(
+    keyboard_input_key_code: Res<Input<KeyCode>>,
+    mut projectile_helper: ResMut<ProjectileHelper>,
+) {
+    if !keyboard_input_key_code.any_just_released([BOMB_BOOSTER_KEYCODE])
+        || projectile_helper.bomb_boosters == 0
+    {
+        return;
+    }
+    projectile_helper.bomb_boosters -= 1;
+    projectile_helper.queue.push_front(Species::Bomb);
+    println!(
+        "Bomb booster used, {} left",
+        projectile_helper.bomb_boosters
+    );
+}
 
-            pkv_play_shoot_audio(&mut commands, &audio_assets, &pkv);
+/// shot fireball becomes a sensor, it is not settled and not swept like other projectiles
+pub fn ignite_fireball(
+    mut commands: Commands,
+    mut shot_fired_events: EventReader<ShotFired>,
+    projectile_query: Query<&Species, With<ProjectileBall>>,
+) {
+    for ShotFired {
+        projectile_entity, ..
+    } in shot_fired_events.iter()
+    {
+        if projectile_query
+            .get(*projectile_entity)
+            .is_ok_and(|species| *species == Species::Fireball)
+        {
+            commands
+                .entity(*projectile_entity)
+                .insert((Fireball, Sensor, GridBallBundle::fireball_layers(false)))
+                .remove::<(SettleDetector, ProjectileSweep)>();
         }
     }
 }
+
+pub fn play_shot_audio(
+    mut commands: Commands,
+    audio_assets: Res<AudioAssets>,
+    pkv: Res<PkvStore>,
+    mut shot_fired_events: EventReader<ShotFired>,
+) {
+    if shot_fired_events.is_empty() {
+        return;
+    }
+    shot_fired_events.clear();
+    pkv_play_shoot_audio(&mut commands, &audio_assets, &pkv);
+}
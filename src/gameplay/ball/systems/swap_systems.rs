@@ -0,0 +1,130 @@
+use bevy::{
+    prelude::{
+        Camera, Commands, Entity, EventReader, EventWriter, GlobalTransform, Input, MouseButton,
+        Query, Res, ResMut, Touches, Transform, Vec2, Vec3, With, Without,
+    },
+    time::{Timer, TimerMode},
+    window::{PrimaryWindow, Window},
+};
+
+use crate::{
+    gameplay::{
+        ball::{
+            components::{
+                AimTarget, GridBallScaleAnimate, NextProjectileBall, ProjectileBall, Species,
+            },
+            projectile_ball_bundle::NextProjectileBallBundle,
+            resources::ProjectileHelper,
+        },
+        constants::{NEXT_PROJECTILE_TOUCH_RADIUS, SWAP_PROJECTILE_TIME},
+        events::SwapProjectile,
+        main_camera::components::MainCamera,
+        materials::resources::GameplayMaterials,
+        utils::detect_pointer_position,
+    },
+    ui::resources::PointerCooldown,
+};
+
+/// right click or tap on next projectile preview
+pub fn detect_swap_projectile(
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<MainCamera>>,
+    mouse_button_input: Res<Input<MouseButton>>,
+    touches: Res<Touches>,
+    next_projectile_query: Query<(&Transform, &NextProjectileBall)>,
+    mut pointer_cooldown: ResMut<PointerCooldown>,
+    mut writer_swap_projectile: EventWriter<SwapProjectile>,
+) {
+    if pointer_cooldown.started {
+        return;
+    }
+    let mut is_swap = mouse_button_input.just_released(MouseButton::Right);
+    let (pointer_position, _, pointer_released, pointer_aquired) =
+        detect_pointer_position(&window_query, &camera_query, &mouse_button_input, &touches);
+    if pointer_aquired && pointer_released {
+        is_swap = is_swap
+            || next_projectile_query
+                .iter()
+                .any(|(transform, next_projectile)| {
+                    next_projectile.index == 0
+                        && transform.translation.truncate().distance(pointer_position)
+                            < NEXT_PROJECTILE_TOUCH_RADIUS
+                });
+    }
+    if is_swap {
+        // cooldown also keeps the same pointer release from shooting
+        pointer_cooldown.started = true;
+        writer_swap_projectile.send(SwapProjectile);
+    }
+}
+
+pub fn swap_projectile(
+    mut commands: Commands,
+    mut swap_projectile_events: EventReader<SwapProjectile>,
+    mut projectile_query: Query<
+        (Entity, &ProjectileBall, &mut Species, &mut Transform),
+        Without<NextProjectileBall>,
+    >,
+    mut next_projectile_query: Query<(Entity, &NextProjectileBall, &mut Species, &mut Transform)>,
+    mut projectile_helper: ResMut<ProjectileHelper>,
+    gameplay_materials: Res<GameplayMaterials>,
+    mut aim_target_query: Query<&mut AimTarget, With<AimTarget>>,
+) {
+    if swap_projectile_events.is_empty() {
+        return;
+    }
+    swap_projectile_events.clear();
+
+    for (projectile_entity, projectile_ball, mut projectile_species, mut projectile_transform) in
+        projectile_query.iter_mut()
+    {
+        if !projectile_ball.state.is_loaded() {
+            println!("Skip swap, projectile {:?} is flying", projectile_entity);
+            continue;
+        }
+        let next_species = match projectile_helper.queue.pop_front() {
+            Some(next_species) => next_species,
+            None => continue,
+        };
+        projectile_helper.queue.push_front(*projectile_species);
+        println!(
+            "Swap projectile {:?} {} with next {}",
+            projectile_entity, *projectile_species, next_species
+        );
+
+        // replace handles, species materials are shared with grid balls
+        for (next_entity, next_projectile, mut species, mut transform) in
+            next_projectile_query.iter_mut()
+        {
+            if next_projectile.index != 0 {
+                continue;
+            }
+            *species = *projectile_species;
+            transform.scale = Vec3::ZERO;
+            commands.entity(next_entity).insert((
+                gameplay_materials.from_species(*projectile_species),
+                build_swap_animate(NextProjectileBallBundle::slot_scale(0)),
+            ));
+        }
+
+        *projectile_species = next_species;
+        projectile_transform.scale = Vec3::ZERO;
+        commands.entity(projectile_entity).insert((
+            gameplay_materials.from_species(next_species),
+            build_swap_animate(Vec2::ONE),
+        ));
+
+        // drop aim which was started by tap on preview
+        if let Ok(mut aim) = aim_target_query.get_single_mut() {
+            aim.pointer_pressed = false;
+            aim.pointer_released = true;
+        }
+    }
+}
+
+fn build_swap_animate(scale: Vec2) -> GridBallScaleAnimate {
+    GridBallScaleAnimate {
+        scale,
+        timer: Timer::from_seconds(SWAP_PROJECTILE_TIME, TimerMode::Once),
+    }
+}
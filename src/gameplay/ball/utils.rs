@@ -1,6 +1,30 @@
-use bevy::prelude::{Commands, DespawnRecursiveExt, Entity, Query, With};
+use bevy::{
+    prelude::{Commands, DespawnRecursiveExt, Entity, Query, Vec2, With, World},
+    utils::HashMap,
+};
+use bevy_xpbd_2d::prelude::Position;
+use fastrand::Rng;
 
-use super::components::{AimLine, NextProjectileBall};
+use crate::gameplay::{
+    constants::{BOMB_QUEUE_CHANCE, FIREBALL_QUEUE_CHANCE, RAINBOW_QUEUE_CHANCE},
+    grid::resources::Grid,
+};
+
+use super::components::{AimLine, NextProjectileBall, Species};
+
+/// queue slot gets one of grid colours, now and then a special projectile
+pub fn pick_queue_species(grid: &Grid, rng: &mut Rng) -> Species {
+    let roll = rng.f32();
+    if roll < BOMB_QUEUE_CHANCE {
+        Species::Bomb
+    } else if roll < BOMB_QUEUE_CHANCE + RAINBOW_QUEUE_CHANCE {
+        Species::Rainbow
+    } else if roll < BOMB_QUEUE_CHANCE + RAINBOW_QUEUE_CHANCE + FIREBALL_QUEUE_CHANCE {
+        Species::Fireball
+    } else {
+        grid.pick_species(rng)
+    }
+}
 
 pub fn cleanup_next_projectile_ball_utils(
     commands: &mut Commands,
@@ -19,3 +43,27 @@ pub fn cleanup_aim_line_utils(
         commands.entity(projectile_entity).despawn_recursive();
     }
 }
+
+/// moves the simulation copy of a real body if it drifted, spawns the copy on first sight
+pub fn sync_aim_mirror(
+    world: &mut World,
+    mirrored: &mut HashMap<Entity, Entity>,
+    entity: Entity,
+    position: Vec2,
+    spawn: impl FnOnce(&mut World) -> Entity,
+) {
+    match mirrored
+        .get(&entity)
+        .and_then(|mirror| world.get_mut::<Position>(*mirror))
+    {
+        Some(mut mirror_position) => {
+            if mirror_position.0 != position {
+                mirror_position.0 = position;
+            }
+        }
+        None => {
+            let mirror = spawn(world);
+            mirrored.insert(entity, mirror);
+        }
+    }
+}
@@ -1,9 +1,10 @@
 use bevy::prelude::KeyCode;
 
+pub const PLAYFIELD_WIDTH: f32 = 408.0;
+pub const PLAYFIELD_HEIGHT: f32 = 755.0;
 pub const CAMERA_SPEED: f32 = 10.0;
 pub const CAMERA_ROTATION_SPEED: f32 = 0.05;
 pub const CAMERA_SCALE: f32 = 0.01;
-pub const COLLISION_SNAP_COOLDOWN_TIME: f32 = 5.0; // seconds
 pub const SIZE: f32 = 23.0;
 pub const ROW_HEIGHT: f32 = 1.5 * SIZE;
 pub const EMPTY_PLAYGROUND_ROWS: u8 = 10;
@@ -12,6 +13,10 @@ pub const PROJECTILE_SPEED: f32 = 1000.;
 pub const MIN_PROJECTILE_SNAP_VELOCITY: f32 = 10.0;
 pub const MIN_PROJECTILE_REVERSE_VELOCITY: f32 = -1.0;
 pub const MIN_PROJECTILE_SNAP_DOT: f32 = 0.1;
+pub const SETTLE_LOW_ENERGY: f32 = 500.0; // 0.5 * m * v², projectile mass is π, so about 18 px/s
+pub const SETTLE_LOW_ENERGY_TIME: f32 = 0.1; // seconds
+pub const SETTLE_TIMEOUT: f32 = 5.0; // seconds after the first touch
+pub const PROJECTILE_SWEEP_RADIUS: f32 = BALL_RADIUS / 2.0; // smaller than ball so sliding along a collider is not taken for tunnelling
 pub const INNER_RADIUS_COEFF: f32 = 0.866025404; // √3 / 2
 pub const BALL_RADIUS: f32 = INNER_RADIUS_COEFF * SIZE;
 pub const NEXT_PROJECTILE_SIZE: f32 = 10.0;
@@ -20,14 +25,27 @@ pub const BALL_DIAMETER: f32 = BALL_RADIUS * 2.0;
 pub const PROJECTILE_SPAWN_BOTTOM: f32 = 40.0;
 pub const NEXT_PROJECTILE_SPAWN_BOTTOM: f32 = 20.0;
 pub const NEXT_PROJECTILE_SPAWN_SIDE: f32 = 40.0;
+pub const NEXT_PROJECTILE_TRAY_GAP: f32 = NEXT_PROJECTILE_RADIUS * 2.0 + 4.0;
+pub const NEXT_PROJECTILE_TRAY_SCALE_STEP: f32 = 0.1; // further queue slots are smaller
 pub const PROJECTILE_SHOOT_BOTTOM: f32 = PROJECTILE_SPAWN_BOTTOM + ROW_HEIGHT * 2.0;
 pub const GAME_OVER_BOTTOM: f32 = PROJECTILE_SPAWN_BOTTOM + ROW_HEIGHT;
 pub const MIN_CLUSTER_SIZE: usize = 3;
-pub const CAST_RAY_TRIES: u32 = 10;
-pub const CAST_RAY_VELOCITY: f32 = BALL_RADIUS;
-pub const CAST_RAY_VELOCITY_TOLERANCE: f32 = 0.1;
-pub const CAST_RAY_MAX_TOI: f32 = EMPTY_PLAYGROUND_HEIGHT * 4.0 / CAST_RAY_VELOCITY;
-pub const CAST_RAY_BOUNCE_Y_ADD: f32 = BALL_RADIUS / 3.9; // devergence between physics actual result and cast ray bounce point
+pub const FLOATING_BALL_SCORE: u32 = 2;
+pub const BOMB_BALL_SCORE: u32 = 3;
+pub const BOMB_BLAST_RINGS: u32 = 2; // hex rings around the impact cleared by bomb
+pub const BOMB_QUEUE_CHANCE: f32 = 0.05; // chance of bomb for every new queue slot
+pub const RAINBOW_QUEUE_CHANCE: f32 = 0.05;
+pub const FIREBALL_QUEUE_CHANCE: f32 = 0.03;
+pub const FIREBALL_BALL_SCORE: u32 = 1;
+pub const ICE_CHANCE: f32 = 0.05; // chance of frozen ball for every generated cell of random grid
+pub const MAX_ICE_LAYERS: u8 = 2;
+pub const CHAMELEON_CHANCE: f32 = 0.04; // chance of chameleon for every generated cell of random grid
+pub const STONE_CHANCE: f32 = 0.04; // chance of stone for every generated cell of random grid
+pub const BOMB_BOOSTERS: u8 = 1; // bombs which player can add to queue per level
+pub const AIM_SIMULATION_TIMESTEP: f32 = 1.0 / 60.0; // same as the real physics timestep
+pub const AIM_SIMULATION_MAX_STEPS: u32 = 240;
+pub const AIM_SIMULATION_COOLDOWN_TIME: f32 = 0.05; // pointer moves are simulated at most this often
+pub const AIM_GHOST_ALPHA: f32 = 0.4;
 pub const LINE_WIDTH: f32 = 4.0;
 pub const WALL_SIDE_WIDTH: f32 = 10.0;
 pub const WALL_SIDE_HEIGHT: f32 = 2500.0;
@@ -37,17 +55,26 @@ pub const MOVE_DOWN_TOLERANCE: f32 = 0.1;
 pub const MAX_APPEAR_TIME: f32 = 2.0;
 pub const APPEAR_TOLERANCE: f32 = 0.1;
 pub const NEIGHBOUR_POSITION_TOLERANCE: f32 = BALL_DIAMETER + BALL_RADIUS / 2.0;
+pub const FREE_HEX_SEARCH_RANGE: u32 = 2;
+pub const NEIGHBOUR_MOVE_TOLERANCE: f32 = 0.5; // ball has to move further to refresh its neighbours
 pub const MAGNETIC_DISTANCE_STRONG: f32 = BALL_DIAMETER * 1.1;
 pub const MAGNETIC_FACTOR_STRONG: f32 = 1000.0;
 pub const MAGNETIC_FACTOR_WEAK: f32 = 500.0;
 pub const CLUSTER_CHECK_COOLDOWN_TIME: f32 = 1.0; // seconds
 pub const OUT_BALL_GRAVITY: f32 = 1000.0;
 pub const FIXED_TIMESTEP: f32 = 0.5;
-pub const PROJECTILE_RELOAD_TIME: f32 = 1.0; // seconds
+pub const PROJECTILE_RELOAD_TIME: f32 = 0.35; // seconds between rapid fire shots
+pub const SWAP_PROJECTILE_TIME: f32 = 0.2; // seconds
+pub const NEXT_PROJECTILE_TOUCH_RADIUS: f32 = BALL_DIAMETER; // preview is small, so tap area is larger
 
 pub const GRID_BALL_Z_INDEX: f32 = 2.0;
 pub const AIM_TARGET_Z_INDEX: f32 = 0.0;
 pub const AIM_LINE_Z_INDEX: f32 = 0.0;
+pub const AIM_GHOST_Z_INDEX: f32 = 1.5;
+pub const POP_HINT_Z_INDEX: f32 = 0.5; // relative to highlighted ball
+pub const ICE_Z_INDEX: f32 = 0.4; // relative to frozen ball
+pub const CHAMELEON_MARK_Z_INDEX: f32 = 0.3; // relative to chameleon ball
+pub const COLLECTIBLE_Z_INDEX: f32 = 0.45; // relative to ball, above ice
 pub const LINE_Z_INDEX: f32 = 0.0;
 pub const NEXT_PROJECTILE_Z_INDEX: f32 = 1.0;
 pub const WALL_Z_INDEX: f32 = 1.0;
@@ -56,3 +83,5 @@ pub const OUT_BALL_Z_INDEX: f32 = 3.0;
 pub const LOG_KEYCODE_CLUSTER: KeyCode = KeyCode::C;
 pub const LOG_KEYCODE_MAGNETIC: KeyCode = KeyCode::M;
 pub const LOG_KEYCODE_RESOURCES: KeyCode = KeyCode::B;
+pub const SWAP_PROJECTILE_KEYCODE: KeyCode = KeyCode::S;
+pub const BOMB_BOOSTER_KEYCODE: KeyCode = KeyCode::X;
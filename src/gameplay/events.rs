@@ -1,11 +1,57 @@
-use bevy::prelude::{Entity, Event};
+use bevy::prelude::{Entity, Event, Vec2};
 
 #[derive(Event)]
 pub struct ProjectileReload;
 
+/// exchange loaded projectile with the next one
+#[derive(Event)]
+pub struct SwapProjectile;
+
+/// why flying projectile was snapped
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SnapReason {
+    /// classic lattice snap on the first touch
+    Touch,
+    /// speed on collision or sustained kinetic energy dropped under threshold
+    Slow,
+    /// projectile turned back after the first touch
+    Reversed,
+    /// too much time passed since the first touch
+    Timeout,
+    /// physics put projectile body to sleep
+    Sleeping,
+}
+
 #[derive(Event)]
 pub struct SnapProjectile {
     pub projectile_entity: Entity,
+    pub reason: SnapReason,
+}
+
+/// loaded projectile was shot
+#[derive(Event)]
+pub struct ShotFired {
+    pub projectile_entity: Entity,
+    pub velocity: Vec2,
+}
+
+/// flying projectile bounced off a side wall
+#[derive(Event)]
+pub struct ProjectileBounced {
+    pub projectile_entity: Entity,
+}
+
+/// projectile snapped and became a grid ball
+#[derive(Event)]
+pub struct ProjectileSettled {
+    pub projectile_entity: Entity,
+    pub reason: SnapReason,
+}
+
+/// projectile was removed in a cluster before it snapped
+#[derive(Event)]
+pub struct ProjectilePopped {
+    pub projectile_entity: Entity,
 }
 
 #[derive(Event)]
@@ -23,3 +69,15 @@ pub struct SpawnRow;
 pub struct FindCluster {
     pub to_check: Entity,
 }
+
+/// cluster popped next to frozen ball, one ice layer cracks
+#[derive(Event)]
+pub struct CrackIce {
+    pub ball_entity: Entity,
+}
+
+/// shoot loaded projectile towards target without pointer, used by headless simulation
+#[derive(Event)]
+pub struct ScriptedShot {
+    pub target: Vec2,
+}
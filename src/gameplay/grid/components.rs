@@ -1,7 +1,31 @@
-use bevy::prelude::Component;
+use bevy::prelude::{Component, Vec2};
 use hexx::Hex;
 
 #[derive(Component)]
 pub struct HexComponent {
     pub hex: Hex,
 }
+
+/// projectile position after the previous physics step, the step path is swept for tunnelling
+#[derive(Component, Default)]
+pub struct ProjectileSweep {
+    pub last_position: Option<Vec2>,
+}
+
+/// each flying projectile settles on its own
+#[derive(Component, Default)]
+pub struct SettleDetector {
+    /// seconds since the first touch with a grid ball
+    pub touched_for: Option<f32>,
+    /// seconds of sustained low kinetic energy
+    pub low_energy_for: f32,
+    pub is_settled: bool,
+}
+
+impl SettleDetector {
+    pub fn touch(&mut self) {
+        if self.touched_for.is_none() {
+            self.touched_for = Some(0.0);
+        }
+    }
+}
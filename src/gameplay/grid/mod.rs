@@ -1,23 +1,36 @@
 use bevy::prelude::{
-    apply_deferred, in_state, App, FixedTime, FixedUpdate, IntoSystemConfigs, OnEnter, OnExit,
-    Plugin, Update,
+    apply_deferred, in_state, App, FixedTime, IntoSystemConfigs, OnEnter, OnExit, Plugin, Update,
 };
+use bevy_xpbd_2d::{PhysicsSchedule, PhysicsStepSet};
 
 use self::{
-    resources::{ClusterCheckCooldown, CollisionSnapCooldown, CooldownMoveCounter, Grid},
+    resources::{ClusterCheckCooldown, CooldownMoveCounter, Grid, SettleThresholds},
     systems::{
         animation_systems::move_down_top_wall,
+        chameleon_systems::cycle_chameleons,
         cluster_systems::find_and_remove_clusters,
-        collision_systems::{check_collision_events, tick_collision_snap_cooldown_timer},
+        collision_systems::{
+            bounce_fireball, burn_grid_balls, check_collision_events, detect_projectile_bounce,
+            detect_projectile_settle, recover_lost_projectile, snap_projectile_to_lattice,
+            sweep_projectile,
+        },
+        ice_systems::crack_ice,
         lifecycle_systems::{cleanup_grid, generate_grid, spawn_new_row},
         magnetic_systems::apply_magnetic_forces,
-        projectile_systems::on_snap_projectile,
+        projectile_systems::{end_projectile_shot, on_snap_projectile},
         resource_systems::update_grid_resources,
         score_systems::update_score_counter,
     },
+    utils::{is_classic_mode, is_magnetic_mode},
 };
 
-use super::{constants::FIXED_TIMESTEP, AppState};
+use super::{
+    constants::FIXED_TIMESTEP,
+    level::systems::apply_level,
+    panels::systems::{count_turns, setup_resources},
+    rng::systems::setup_game_rng,
+    AppState,
+};
 
 pub mod components;
 pub mod resources;
@@ -29,32 +42,59 @@ pub struct GridPlugin;
 impl Plugin for GridPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<Grid>()
-            .init_resource::<CollisionSnapCooldown>()
             .init_resource::<ClusterCheckCooldown>()
             .init_resource::<CooldownMoveCounter>()
-            .add_systems(OnEnter(AppState::GameplayInit), generate_grid)
+            .init_resource::<SettleThresholds>()
+            .add_systems(
+                OnEnter(AppState::GameplayInit),
+                // grid settings, level and seed have to be ready before the first row
+                generate_grid
+                    .after(setup_resources)
+                    .after(apply_level)
+                    .after(setup_game_rng),
+            )
             .add_systems(
                 Update,
                 (
                     move_down_top_wall,
-                    check_collision_events,
-                    tick_collision_snap_cooldown_timer,
+                    check_collision_events.run_if(is_magnetic_mode),
+                    snap_projectile_to_lattice
+                        .run_if(is_classic_mode)
+                        .before(on_snap_projectile),
+                    detect_projectile_settle.run_if(is_magnetic_mode),
+                    detect_projectile_bounce,
+                    burn_grid_balls.before(find_and_remove_clusters),
+                    bounce_fireball,
+                    crack_ice.after(find_and_remove_clusters),
+                    // new colours go to grid resources in the same frame
+                    cycle_chameleons
+                        .after(count_turns)
+                        .before(update_grid_resources),
+                    recover_lost_projectile,
+                    end_projectile_shot.after(find_and_remove_clusters),
                     update_score_counter,
                     spawn_new_row,
-                    apply_magnetic_forces,
+                    apply_magnetic_forces.after(update_grid_resources),
                 )
                     .run_if(in_state(AppState::Gameplay)),
             )
             .add_systems(
                 Update,
                 // snap projectile generate new grid ball, we need to use this ball in clusters, so wait after commands
-                (on_snap_projectile, apply_deferred, find_and_remove_clusters)
+                (
+                    on_snap_projectile,
+                    apply_deferred,
+                    update_grid_resources,
+                    find_and_remove_clusters,
+                )
                     .chain()
                     .run_if(in_state(AppState::Gameplay)),
             )
             .add_systems(
-                FixedUpdate,
-                update_grid_resources.run_if(in_state(AppState::Gameplay)),
+                PhysicsSchedule,
+                sweep_projectile
+                    .after(PhysicsStepSet::SpatialQuery)
+                    .run_if(in_state(AppState::Gameplay)),
             )
             .insert_resource(FixedTime::new_from_secs(FIXED_TIMESTEP))
             .add_systems(OnExit(AppState::Gameplay), cleanup_grid);
@@ -1,13 +1,21 @@
+use std::fmt::{Display, Formatter, Result};
+
 use bevy::{
     prelude::{Entity, Resource, Vec2},
     time::{Timer, TimerMode},
     utils::{default, HashMap, HashSet},
 };
-use hexx::{HexLayout, HexOrientation, OffsetHexMode};
+use fastrand::Rng;
+use hexx::{Hex, HexLayout, HexOrientation, OffsetHexMode};
 
 use crate::gameplay::{
-    ball::components::Species,
-    constants::{CLUSTER_CHECK_COOLDOWN_TIME, COLLISION_SNAP_COOLDOWN_TIME, SIZE},
+    ball::components::{ProjectileBall, Species},
+    constants::{
+        BALL_DIAMETER, CHAMELEON_CHANCE, CLUSTER_CHECK_COOLDOWN_TIME, ICE_CHANCE, MAX_ICE_LAYERS,
+        MIN_PROJECTILE_REVERSE_VELOCITY, MIN_PROJECTILE_SNAP_VELOCITY, NEIGHBOUR_MOVE_TOLERANCE,
+        NEIGHBOUR_POSITION_TOLERANCE, SETTLE_LOW_ENERGY, SETTLE_LOW_ENERGY_TIME, SETTLE_TIMEOUT,
+        SIZE, STONE_CHANCE,
+    },
 };
 
 #[derive(Default, Debug, Copy, Clone)]
@@ -31,8 +39,125 @@ pub struct Bounds {
     pub dirty: bool,
 }
 
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GridMode {
+    /// balls are dynamic bodies attracted to each other
+    #[default]
+    Magnetic,
+    /// balls are kinematic and placed exactly into hex lattice
+    Classic,
+}
+
+impl Display for GridMode {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        match self {
+            GridMode::Magnetic => write!(f, "magnetic"),
+            GridMode::Classic => write!(f, "classic"),
+        }
+    }
+}
+
+impl From<&str> for GridMode {
+    fn from(value: &str) -> Self {
+        match value {
+            "classic" => GridMode::Classic,
+            _ => GridMode::Magnetic,
+        }
+    }
+}
+
+/// buckets balls into cells of neighbour size, so neighbours are searched only in nearby cells
+#[derive(Default)]
+pub struct SpatialIndex {
+    pub cells: HashMap<(i32, i32), Vec<Entity>>,
+    pub entities_to_cells: HashMap<Entity, (i32, i32)>,
+    pub entities_to_indexed_positions: HashMap<Entity, Vec2>,
+}
+
+impl SpatialIndex {
+    pub fn cell(position: Vec2) -> (i32, i32) {
+        (
+            (position.x / NEIGHBOUR_POSITION_TOLERANCE).floor() as i32,
+            (position.y / NEIGHBOUR_POSITION_TOLERANCE).floor() as i32,
+        )
+    }
+
+    /// returns true if ball is new or moved far enough to refresh neighbours
+    pub fn update(&mut self, entity: Entity, position: Vec2) -> bool {
+        if let Some(indexed_position) = self.entities_to_indexed_positions.get(&entity) {
+            if indexed_position.distance(position) < NEIGHBOUR_MOVE_TOLERANCE {
+                return false;
+            }
+        }
+        let cell = Self::cell(position);
+        if let Some(prev_cell) = self.entities_to_cells.insert(entity, cell) {
+            if prev_cell != cell {
+                self.remove_from_cell(entity, prev_cell);
+                self.cells.entry(cell).or_default().push(entity);
+            }
+        } else {
+            self.cells.entry(cell).or_default().push(entity);
+        }
+        self.entities_to_indexed_positions.insert(entity, position);
+        true
+    }
+
+    pub fn remove(&mut self, entity: Entity) {
+        if let Some(cell) = self.entities_to_cells.remove(&entity) {
+            self.remove_from_cell(entity, cell);
+        }
+        self.entities_to_indexed_positions.remove(&entity);
+    }
+
+    fn remove_from_cell(&mut self, entity: Entity, cell: (i32, i32)) {
+        if let Some(entities) = self.cells.get_mut(&cell) {
+            entities.retain(|cell_entity| *cell_entity != entity);
+            if entities.is_empty() {
+                self.cells.remove(&cell);
+            }
+        }
+    }
+
+    /// balls within neighbour tolerance of position sorted by distance
+    pub fn find_near(&self, position: Vec2) -> Vec<(Entity, f32)> {
+        let mut near: Vec<(Entity, f32)> = vec![];
+        let (cell_x, cell_y) = Self::cell(position);
+        for x in cell_x - 1..=cell_x + 1 {
+            for y in cell_y - 1..=cell_y + 1 {
+                if let Some(entities) = self.cells.get(&(x, y)) {
+                    for entity in entities.iter() {
+                        if let Some(indexed_position) =
+                            self.entities_to_indexed_positions.get(entity)
+                        {
+                            let distance = position.distance(*indexed_position);
+                            if distance <= NEIGHBOUR_POSITION_TOLERANCE {
+                                near.push((*entity, distance));
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        near.sort_by(|(_, distance_a), (_, distance_b)| distance_a.total_cmp(distance_b));
+        near
+    }
+
+    /// balls within neighbour tolerance sorted by distance
+    pub fn find_neighbours(&self, entity: Entity) -> Vec<(Entity, f32)> {
+        match self.entities_to_indexed_positions.get(&entity) {
+            Some(position) => self
+                .find_near(*position)
+                .into_iter()
+                .filter(|(neighbour, _)| *neighbour != entity)
+                .collect(),
+            None => vec![],
+        }
+    }
+}
+
 #[derive(Resource)]
 pub struct Grid {
+    pub mode: GridMode,
     pub init_cols: u8,
     pub init_rows: u8,
     pub total_rows: u8,
@@ -45,6 +170,27 @@ pub struct Grid {
     pub entities_to_species: HashMap<Entity, Species>,
     pub active_species: HashSet<Species>,
     pub entities_to_neighbours: HashMap<Entity, Vec<(Entity, f32)>>,
+    pub spatial_index: SpatialIndex,
+    pub entities_to_hexes: HashMap<Entity, Hex>,
+    pub hexes_to_entities: HashMap<Hex, Entity>,
+    /// level colour set, empty for random grid
+    pub colors: Vec<Species>,
+    /// level cells, None means empty cell
+    pub level_cells: HashMap<Hex, Option<Species>>,
+    /// chance of stone for generated cells
+    pub stone_chance: f32,
+    /// level ice layers by cell
+    pub level_ice: HashMap<Hex, u8>,
+    /// chance of frozen ball for generated cells
+    pub ice_chance: f32,
+    /// frozen balls are skipped by clusters
+    pub frozen: HashSet<Entity>,
+    /// level cells with chameleon ball
+    pub level_chameleons: HashSet<Hex>,
+    /// chance of chameleon for generated cells
+    pub chameleon_chance: f32,
+    /// level cells with collectible, random grid has none
+    pub level_collectibles: HashSet<Hex>,
 }
 
 impl Default for Grid {
@@ -57,6 +203,7 @@ impl Default for Grid {
             origin: Vec2::ZERO,
         };
         Self {
+            mode: GridMode::default(),
             init_cols: default(),
             init_rows: default(),
             total_rows: default(),
@@ -69,6 +216,18 @@ impl Default for Grid {
             entities_to_species: HashMap::default(),
             active_species: HashSet::default(),
             entities_to_neighbours: HashMap::default(),
+            spatial_index: SpatialIndex::default(),
+            entities_to_hexes: HashMap::default(),
+            hexes_to_entities: HashMap::default(),
+            colors: vec![],
+            level_cells: HashMap::default(),
+            stone_chance: STONE_CHANCE,
+            level_ice: HashMap::default(),
+            ice_chance: ICE_CHANCE,
+            frozen: HashSet::default(),
+            level_chameleons: HashSet::default(),
+            chameleon_chance: CHAMELEON_CHANCE,
+            level_collectibles: HashSet::default(),
         }
     }
 }
@@ -85,6 +244,119 @@ impl Grid {
     pub fn clear(&mut self) {
         *self = Self::default();
     }
+
+    pub fn random_species(&self, rng: &mut Rng) -> Species {
+        match self.colors.is_empty() {
+            true => Species::random_species(self.total_colors, rng),
+            false => self.colors[rng.usize(..self.colors.len())],
+        }
+    }
+
+    /// generated cell gets a stone now and then
+    pub fn random_cell_species(&self, rng: &mut Rng) -> Species {
+        match self.stone_chance > 0.0 && rng.f32() < self.stone_chance {
+            true => Species::Stone,
+            false => self.random_species(rng),
+        }
+    }
+
+    /// generated cell gets one or more ice layers now and then
+    pub fn random_ice_layers(&self, rng: &mut Rng) -> u8 {
+        match self.ice_chance > 0.0 && rng.f32() < self.ice_chance {
+            true => rng.u8(1..=MAX_ICE_LAYERS),
+            false => 0,
+        }
+    }
+
+    /// generated cell gets a chameleon now and then
+    pub fn random_chameleon(&self, rng: &mut Rng) -> bool {
+        self.chameleon_chance > 0.0 && rng.f32() < self.chameleon_chance
+    }
+
+    /// projectile gets one of species left in grid
+    pub fn pick_species(&self, rng: &mut Rng) -> Species {
+        match self.active_species.is_empty() {
+            true => self.random_species(rng),
+            false => Species::pick_random(&self.active_species, self.total_colors, rng),
+        }
+    }
+
+    /// returns true if ball is new in lattice or changed its hex
+    pub fn update_hex(&mut self, entity: Entity, hex: Hex) -> bool {
+        match self.entities_to_hexes.insert(entity, hex) {
+            Some(prev_hex) if prev_hex == hex => false,
+            some_prev_hex => {
+                if let Some(prev_hex) = some_prev_hex {
+                    self.hexes_to_entities.remove(&prev_hex);
+                }
+                self.hexes_to_entities.insert(hex, entity);
+                true
+            }
+        }
+    }
+
+    /// lattice balls are adjacent by hex, others by distance
+    pub fn find_neighbours(&self, entity: Entity) -> Vec<(Entity, f32)> {
+        match self.entities_to_hexes.get(&entity) {
+            Some(hex) => hex
+                .all_neighbors()
+                .iter()
+                .filter_map(|neighbour_hex| self.hexes_to_entities.get(neighbour_hex))
+                .map(|neighbour| (*neighbour, BALL_DIAMETER))
+                .collect(),
+            None => self.spatial_index.find_neighbours(entity),
+        }
+    }
+
+    pub fn is_hex_in_bounds(&self, hex: Hex) -> bool {
+        let max_side_x = (self.init_cols / 2) as i32;
+        let is_even = hex.y % 2 == 0;
+        let offset = hex.to_offset_coordinates(self.offset_mode);
+        hex.y >= self.last_active_row
+            && offset[0] >= -max_side_x
+            && offset[0] <= max_side_x
+            && (is_even || offset[0] != max_side_x)
+    }
+
+    /// returns former neighbours, they have to be refreshed
+    pub fn remove_ball(&mut self, entity: Entity) -> Vec<Entity> {
+        self.entities_to_positions.remove(&entity);
+        self.frozen.remove(&entity);
+        self.entities_to_species.remove(&entity);
+        self.spatial_index.remove(entity);
+        if let Some(hex) = self.entities_to_hexes.remove(&entity) {
+            self.hexes_to_entities.remove(&hex);
+        }
+        match self.entities_to_neighbours.remove(&entity) {
+            Some(neighbours) => neighbours.iter().map(|(neighbour, _)| *neighbour).collect(),
+            None => vec![],
+        }
+    }
+
+    pub fn refresh_neighbours(&mut self, moved: &HashSet<Entity>) {
+        // distances to moved balls are changed for old and new neighbours as well
+        let mut to_refresh: HashSet<Entity> = moved.clone();
+        for entity in moved.iter() {
+            if let Some(neighbours) = self.entities_to_neighbours.get(entity) {
+                to_refresh.extend(neighbours.iter().map(|(neighbour, _)| *neighbour));
+            }
+            to_refresh.extend(
+                self.find_neighbours(*entity)
+                    .iter()
+                    .map(|(neighbour, _)| *neighbour),
+            );
+        }
+        for entity in to_refresh.iter() {
+            if self.spatial_index.entities_to_cells.contains_key(entity)
+                || self.entities_to_hexes.contains_key(entity)
+            {
+                let neighbours = self.find_neighbours(*entity);
+                self.entities_to_neighbours.insert(*entity, neighbours);
+            } else {
+                self.entities_to_neighbours.remove(entity);
+            }
+        }
+    }
 }
 
 #[derive(Resource)]
@@ -115,91 +387,70 @@ impl CooldownMoveCounter {
     }
 }
 
-pub struct CheckAt {
-    ms_time: u32,
-    checked: bool,
+#[derive(Resource)]
+pub struct ClusterCheckCooldown {
+    pub timer: Timer,
+    pub to_check: HashSet<Entity>,
+    /// balls touched by fireball, removed one by one regardless of colour
+    pub to_burn: HashSet<Entity>,
 }
 
-impl CheckAt {
-    pub fn new(ms_time: u32) -> Self {
+impl Default for ClusterCheckCooldown {
+    fn default() -> Self {
         Self {
-            ms_time,
-            checked: false,
+            timer: Timer::from_seconds(CLUSTER_CHECK_COOLDOWN_TIME, TimerMode::Repeating),
+            to_check: default(),
+            to_burn: default(),
         }
     }
 }
 
-#[derive(Resource)]
-pub struct CollisionSnapCooldown {
-    pub timer: Timer,
-    pub check_at: Vec<CheckAt>,
+/// when flying projectile counts as settled and gets snapped
+#[derive(Resource, Debug, Clone, Copy)]
+pub struct SettleThresholds {
+    /// speed on collision under which projectile is slow
+    pub slow_velocity: f32,
+    /// downward speed after the first touch which means projectile turned back
+    pub reverse_velocity: f32,
+    /// kinetic energy under which projectile is settling
+    pub low_energy: f32,
+    /// seconds of sustained low kinetic energy to settle
+    pub low_energy_time: f32,
+    /// seconds after the first touch to snap anyway
+    pub timeout: f32,
 }
 
-impl Default for CollisionSnapCooldown {
+impl Default for SettleThresholds {
     fn default() -> Self {
-        let mut timer = Timer::from_seconds(COLLISION_SNAP_COOLDOWN_TIME, TimerMode::Repeating);
-        timer.pause();
         Self {
-            timer,
-            check_at: vec![
-                CheckAt::new(1000),
-                CheckAt::new(2000),
-                CheckAt::new(3000),
-                CheckAt::new(4000),
-            ],
+            slow_velocity: MIN_PROJECTILE_SNAP_VELOCITY,
+            reverse_velocity: MIN_PROJECTILE_REVERSE_VELOCITY,
+            low_energy: SETTLE_LOW_ENERGY,
+            low_energy_time: SETTLE_LOW_ENERGY_TIME,
+            timeout: SETTLE_TIMEOUT,
         }
     }
 }
 
-impl CollisionSnapCooldown {
-    pub fn start(&mut self) {
-        self.restart();
-        self.timer.unpause();
+impl SettleThresholds {
+    pub fn is_slow(&self, velocity: Vec2) -> bool {
+        velocity.length() <= self.slow_velocity
     }
 
-    pub fn stop(&mut self) {
-        println!("CollisionSnapCooldown stop");
-        self.timer.pause();
-    }
-
-    pub fn restart(&mut self) {
-        self.timer.reset();
-        self.timer.pause();
-        self.check_at = (0u32..=(COLLISION_SNAP_COOLDOWN_TIME * 1000.0).round() as u32)
-            // each 100 ms check
-            .step_by(100)
-            .map(|t| CheckAt::new(t))
-            .collect::<Vec<CheckAt>>();
-    }
-
-    pub fn is_ready_for_check(&mut self, mut check_fn: impl FnMut() -> bool) -> bool {
-        let elapsed_ms = self.timer.elapsed().as_millis() as u32;
-        let mut is_ready = self.timer.finished();
-        if !is_ready {
-            for check_at in self.check_at.iter_mut() {
-                if !check_at.checked && elapsed_ms > check_at.ms_time {
-                    (*check_at).checked = true;
-                    is_ready = check_fn();
-                    // check only once per function call
-                    break;
-                }
+    /// detect that projectile after snap is moving into the same clockwise/counterclockwise direction around snap grid ball
+    pub fn is_reversed(&self, projectile_ball: &mut ProjectileBall, velocity: Vec2) -> bool {
+        if projectile_ball.snap_vel == Vec2::ZERO {
+            projectile_ball.snap_vel = velocity.normalize();
+        } else {
+            if velocity.y < self.reverse_velocity {
+                return true;
+            }
+            let dot = projectile_ball.snap_vel.dot(velocity.normalize());
+            println!("dot {}", dot);
+            if dot < 0.0 {
+                return true;
             }
         }
-        is_ready
-    }
-}
-
-#[derive(Resource)]
-pub struct ClusterCheckCooldown {
-    pub timer: Timer,
-    pub to_check: HashSet<Entity>,
-}
-
-impl Default for ClusterCheckCooldown {
-    fn default() -> Self {
-        Self {
-            timer: Timer::from_seconds(CLUSTER_CHECK_COOLDOWN_TIME, TimerMode::Repeating),
-            to_check: default(),
-        }
+        false
     }
 }
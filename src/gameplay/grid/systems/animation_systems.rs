@@ -1,6 +1,5 @@
-use bevy::{
-    prelude::{Commands, Entity, EventReader, EventWriter, Query, ResMut, Vec2, With, Without},
-    window::{PrimaryWindow, Window},
+use bevy::prelude::{
+    Commands, Entity, EventReader, EventWriter, Query, Res, ResMut, Vec2, With, Without,
 };
 use bevy_xpbd_2d::prelude::{LinearVelocity, Position, RigidBody};
 
@@ -9,10 +8,12 @@ use crate::gameplay::{
     constants::{MOVE_DOWN_TOLERANCE, ROW_HEIGHT},
     events::{MoveDownTopWall, SpawnRow},
     grid::{
+        components::HexComponent,
         resources::{CooldownMoveCounter, Grid},
         utils::adjust_grid_layout,
     },
     panels::resources::MoveDownCounter,
+    playfield::resources::Playfield,
     walls::components::{TopWall, TopWallPositionAnimate},
 };
 
@@ -28,11 +29,16 @@ pub fn move_down_top_wall(
         With<TopWall>,
     >,
     mut balls_query: Query<
-        (&RigidBody, &mut Position, &mut LinearVelocity),
+        (
+            &RigidBody,
+            &mut Position,
+            &mut LinearVelocity,
+            Option<&HexComponent>,
+        ),
         (With<GridBall>, Without<TopWall>),
     >,
     mut move_down_events: EventReader<MoveDownTopWall>,
-    window_query: Query<&Window, With<PrimaryWindow>>,
+    playfield: Res<Playfield>,
     mut grid: ResMut<Grid>,
     mut move_counter: ResMut<MoveDownCounter>,
     mut cooldown_move_counter: ResMut<CooldownMoveCounter>,
@@ -44,7 +50,7 @@ pub fn move_down_top_wall(
             move_counter.0 += 1;
             cooldown_move_counter.reset();
 
-            adjust_grid_layout(&window_query, &mut grid, move_counter.0);
+            adjust_grid_layout(&playfield, &mut grid, move_counter.0);
             for (wall_entity, wall_position, some_wall_animate, _) in top_wall_query.iter() {
                 let position = match some_wall_animate {
                     Some(wall_animate) => wall_animate.position,
@@ -61,7 +67,7 @@ pub fn move_down_top_wall(
     {
         if let Some(wall_animate) = some_wall_animate {
             wall_linear_velocity.0 = wall_animate.position - wall_position.0;
-            for (rigid_body, _, mut ball_linear_velocity) in balls_query.iter_mut() {
+            for (rigid_body, _, mut ball_linear_velocity, _) in balls_query.iter_mut() {
                 if rigid_body.is_kinematic() {
                     ball_linear_velocity.0 = wall_linear_velocity.0
                 }
@@ -75,12 +81,18 @@ pub fn move_down_top_wall(
                 wall_position.0 = wall_animate.position;
 
                 writer_spawn_row.send(SpawnRow);
-                for (rigid_body, mut ball_position, mut ball_linear_velocity) in
+                for (rigid_body, mut ball_position, mut ball_linear_velocity, some_hex) in
                     balls_query.iter_mut()
                 {
                     if rigid_body.is_kinematic() {
                         ball_linear_velocity.0 = Vec2::ZERO;
-                        ball_position.y = wall_animate.position.y - ROW_HEIGHT;
+                        match some_hex {
+                            // layout is already moved down
+                            Some(hex_component) => {
+                                ball_position.0 = grid.layout.hex_to_world_pos(hex_component.hex)
+                            }
+                            None => ball_position.y = wall_animate.position.y - ROW_HEIGHT,
+                        }
                     }
                 }
             }
@@ -0,0 +1,50 @@
+use bevy::prelude::{Commands, Entity, Local, Query, Res, With};
+
+use crate::gameplay::{
+    ball::components::{Chameleon, Frozen, Species},
+    grid::resources::Grid,
+    materials::resources::GameplayMaterials,
+    panels::resources::TurnCounter,
+};
+
+/// chameleon balls take the next active colour once per finished turn, frozen ones keep it
+pub fn cycle_chameleons(
+    mut commands: Commands,
+    mut last_turn: Local<u32>,
+    turn_counter: Res<TurnCounter>,
+    mut chameleons_query: Query<(Entity, &mut Species, Option<&Frozen>), With<Chameleon>>,
+    grid: Res<Grid>,
+    gameplay_materials: Res<GameplayMaterials>,
+) {
+    // turn counter starts from zero again on restart
+    if turn_counter.0 < *last_turn {
+        *last_turn = 0;
+    }
+    let turns = turn_counter.0 - *last_turn;
+    *last_turn = turn_counter.0;
+    if turns == 0 {
+        return;
+    }
+    for (entity, mut species, frozen) in chameleons_query.iter_mut() {
+        if frozen.is_some() {
+            continue;
+        }
+        let mut next_species = *species;
+        for _ in 0..turns {
+            if let Some(active) = next_species.next_active(&grid.active_species) {
+                next_species = active;
+            }
+        }
+        if next_species == *species {
+            continue;
+        }
+        println!(
+            "Chameleon {:?} turned from {:?} to {:?}",
+            entity, *species, next_species
+        );
+        *species = next_species;
+        commands
+            .entity(entity)
+            .insert(gameplay_materials.from_species(next_species));
+    }
+}
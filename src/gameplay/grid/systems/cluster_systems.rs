@@ -1,4 +1,5 @@
 use bevy::{
+    ecs::system::SystemParam,
     prelude::{
         Assets, Commands, DespawnRecursiveExt, Entity, EventReader, EventWriter, Input, KeyCode,
         Query, Res, ResMut, With,
@@ -11,22 +12,80 @@ use bevy_xpbd_2d::prelude::{LinearVelocity, Position, RigidBody};
 
 use crate::gameplay::{
     ball::{
-        components::{GridBall, ProjectileBall, Species},
+        components::{
+            BlastOutBall, GridBall, MatchedOutBall, ProjectileBall, ProjectileState, Species,
+        },
         out_ball_bundle::OutBallBundle,
     },
-    constants::{LOG_KEYCODE_CLUSTER, MIN_CLUSTER_SIZE},
-    events::{FindCluster, ProjectileReload, UpdateScoreCounter},
+    constants::{
+        BOMB_BALL_SCORE, FIREBALL_BALL_SCORE, FLOATING_BALL_SCORE, LOG_KEYCODE_CLUSTER,
+        MIN_CLUSTER_SIZE, NEIGHBOUR_POSITION_TOLERANCE,
+    },
+    events::{CrackIce, FindCluster, ProjectilePopped, UpdateScoreCounter},
     grid::{
-        resources::{ClusterCheckCooldown, CollisionSnapCooldown, Grid},
-        utils::find_cluster,
+        resources::{ClusterCheckCooldown, Grid},
+        utils::{find_anchors, find_blast, find_cluster, find_floating},
     },
     meshes::resources::GameplayMeshes,
-    panels::resources::TurnCounter,
 };
 
+/// cluster checks wait for the cooldown, burnt balls are checked right away
+#[derive(SystemParam)]
+pub struct ClusterChecks<'w, 's> {
+    find_cluster_events: EventReader<'w, 's, FindCluster>,
+    cluster_check_cooldown: ResMut<'w, ClusterCheckCooldown>,
+    time: Res<'w, Time>,
+}
+
+impl ClusterChecks<'_, '_> {
+    /// collects balls to check, returns true when they have to be checked in this frame
+    fn is_flush_due(&mut self) -> bool {
+        let cluster_check_cooldown = &mut self.cluster_check_cooldown;
+        let is_paused = cluster_check_cooldown.timer.paused();
+        if !is_paused {
+            cluster_check_cooldown.timer.tick(self.time.delta());
+        }
+        let mut flush_checks = false;
+        for FindCluster { to_check } in self.find_cluster_events.iter() {
+            cluster_check_cooldown.to_check.insert(*to_check);
+            if is_paused {
+                flush_checks = true;
+                cluster_check_cooldown.timer.unpause();
+            }
+        }
+
+        // burnt balls go right away while fireball passes through
+        if !cluster_check_cooldown.to_burn.is_empty() {
+            flush_checks = true;
+        }
+
+        if cluster_check_cooldown.timer.just_finished() {
+            flush_checks = true;
+            cluster_check_cooldown.timer.reset();
+            cluster_check_cooldown.timer.pause();
+        }
+        flush_checks
+    }
+}
+
+/// removed balls fly out as separate entities
+#[derive(SystemParam)]
+pub struct OutBallAssets<'w> {
+    gameplay_meshes: Res<'w, GameplayMeshes>,
+    materials: ResMut<'w, Assets<ColorMaterial>>,
+}
+
+/// removed balls update score, end the shot and crack ice around
+#[derive(SystemParam)]
+pub struct ClusterWriters<'w> {
+    writer_update_cooldown_counter: EventWriter<'w, UpdateScoreCounter>,
+    writer_projectile_popped: EventWriter<'w, ProjectilePopped>,
+    writer_crack_ice: EventWriter<'w, CrackIce>,
+}
+
 pub fn find_and_remove_clusters(
     mut commands: Commands,
-    mut find_cluster_events: EventReader<FindCluster>,
+    mut cluster_checks: ClusterChecks,
     mut balls_query: Query<
         (
             Entity,
@@ -35,53 +94,67 @@ pub fn find_and_remove_clusters(
             &Species,
             &mut GridBall,
             &mut RigidBody,
-            Option<&ProjectileBall>,
+            Option<&mut ProjectileBall>,
         ),
         With<GridBall>,
     >,
-    gameplay_meshes: Res<GameplayMeshes>,
-    mut materials: ResMut<Assets<ColorMaterial>>,
-    mut writer_update_cooldown_counter: EventWriter<UpdateScoreCounter>,
-    mut collision_snap_cooldown: ResMut<CollisionSnapCooldown>,
-    mut turn_counter: ResMut<TurnCounter>,
-    mut projectile_reload_writer: EventWriter<ProjectileReload>,
+    out_ball_assets: OutBallAssets,
+    cluster_writers: ClusterWriters,
     grid: Res<Grid>,
     keyboard_input_key_code: Res<Input<KeyCode>>,
-    time: Res<Time>,
-    mut cluster_check_cooldown: ResMut<ClusterCheckCooldown>,
 ) {
-    let is_paused = cluster_check_cooldown.timer.paused();
-    if !is_paused {
-        cluster_check_cooldown.timer.tick(time.delta());
-    }
-    let mut flush_checks = false;
-    for FindCluster { to_check } in find_cluster_events.iter() {
-        cluster_check_cooldown.to_check.insert(*to_check);
-        if is_paused {
-            flush_checks = true;
-            cluster_check_cooldown.timer.unpause();
-        }
-    }
-
-    if cluster_check_cooldown.timer.just_finished() {
-        flush_checks = true;
-        cluster_check_cooldown.timer.reset();
-        cluster_check_cooldown.timer.pause();
-    }
+    let flush_checks = cluster_checks.is_flush_due();
+    let cluster_check_cooldown = &mut cluster_checks.cluster_check_cooldown;
+    let OutBallAssets {
+        gameplay_meshes,
+        mut materials,
+    } = out_ball_assets;
+    let ClusterWriters {
+        mut writer_update_cooldown_counter,
+        mut writer_projectile_popped,
+        mut writer_crack_ice,
+    } = cluster_writers;
 
     if flush_checks {
+        let mut removed: HashSet<Entity> = HashSet::default();
         for start_from in cluster_check_cooldown.to_check.iter() {
-            let (cluster, _) = find_cluster(
-                *start_from,
-                &grid.entities_to_neighbours,
-                &grid.entities_to_species,
-            );
+            // bomb clears balls around and fireball burns single balls regardless of colour
+            let is_bomb = grid.entities_to_species.get(start_from) == Some(&Species::Bomb);
+            let is_burnt = cluster_check_cooldown.to_burn.contains(start_from);
+            let cluster = match (is_bomb, grid.entities_to_positions.get(start_from)) {
+                _ if is_burnt => HashSet::from_iter([*start_from]),
+                (true, Some(bomb_position)) => find_blast(
+                    &grid,
+                    *start_from,
+                    *bomb_position,
+                    grid.entities_to_neighbours
+                        .get(start_from)
+                        .map(Vec::as_slice)
+                        .unwrap_or_default(),
+                ),
+                _ => {
+                    let (cluster, _) = find_cluster(
+                        *start_from,
+                        &grid.entities_to_neighbours,
+                        &grid.entities_to_species,
+                        &grid.frozen,
+                    );
+                    cluster
+                }
+            };
             if keyboard_input_key_code.any_pressed([LOG_KEYCODE_CLUSTER]) {
                 println!("cluster {:?}", cluster);
             }
 
+            // rainbow joins the colour of the rest of its cluster
+            let matched_species = cluster
+                .iter()
+                .filter_map(|cluster_entity| grid.entities_to_species.get(cluster_entity))
+                .find(|species| **species != Species::Rainbow)
+                .copied();
+
             let mut cluster_score_add = 0;
-            if cluster.len() >= MIN_CLUSTER_SIZE {
+            if is_bomb || is_burnt || cluster.len() >= MIN_CLUSTER_SIZE {
                 // remove matching cluster
                 cluster.iter().for_each(|cluster_entity| {
                     if let Ok((
@@ -91,36 +164,115 @@ pub fn find_and_remove_clusters(
                         cluster_species,
                         mut grid_ball,
                         _,
-                        some_projectile_ball,
+                        mut some_projectile_ball,
                     )) = balls_query.get_mut(*cluster_entity)
                     {
                         if !grid_ball.is_ready_to_despawn {
                             grid_ball.is_ready_to_despawn = true;
-                            commands.spawn(OutBallBundle::new(
+                            let mut out_ball = commands.spawn(OutBallBundle::new(
                                 cluster_position.0,
                                 *cluster_species,
                                 &gameplay_meshes,
                                 &mut materials,
                                 false,
                             ));
+                            if is_bomb || is_burnt {
+                                out_ball.insert(BlastOutBall);
+                            } else if let (Species::Rainbow, Some(matched_species)) =
+                                (cluster_species, matched_species)
+                            {
+                                out_ball.insert(MatchedOutBall(matched_species));
+                            }
                             println!("cluster entity despawned {:?}", cluster_entity);
                             commands.entity(cluster_entity).despawn_recursive();
-                            cluster_score_add += 1;
-                            if some_projectile_ball.is_some() {
+                            cluster_score_add += if is_bomb {
+                                BOMB_BALL_SCORE
+                            } else if is_burnt {
+                                FIREBALL_BALL_SCORE
+                            } else {
+                                1
+                            };
+                            removed.insert(cluster_entity);
+                            if let Some(projectile_ball) = some_projectile_ball.as_mut() {
                                 println!("projectile removed in cluster {:?}", cluster_entity);
-                                turn_counter.0 += 1;
-                                collision_snap_cooldown.stop();
-                                projectile_reload_writer.send(ProjectileReload);
+                                projectile_ball.state = ProjectileState::Popped;
+                                writer_projectile_popped.send(ProjectilePopped {
+                                    projectile_entity: cluster_entity,
+                                });
                             }
                         }
                     }
                 });
             }
 
+            if cluster_score_add > 0 {
+                // pop cracks one ice layer of each adjacent frozen ball
+                let cracked: HashSet<Entity> = cluster
+                    .iter()
+                    .filter_map(|cluster_entity| grid.entities_to_neighbours.get(cluster_entity))
+                    .flatten()
+                    .filter(|(neighbour, distance)| {
+                        *distance <= NEIGHBOUR_POSITION_TOLERANCE
+                            && grid.frozen.contains(neighbour)
+                            && !cluster.contains(neighbour)
+                    })
+                    .map(|(neighbour, _)| *neighbour)
+                    .collect();
+                for ball_entity in cracked {
+                    writer_crack_ice.send(CrackIce { ball_entity });
+                }
+            }
+
             writer_update_cooldown_counter.send(UpdateScoreCounter {
                 score_add: cluster_score_add,
             });
         }
+
+        if !removed.is_empty() {
+            // drop balls which are no longer connected to the top row
+            let anchors = find_anchors(&grid, |entity| match balls_query.get(entity) {
+                Ok((_, _, _, _, _, rigid_body, _)) => rigid_body.is_kinematic(),
+                Err(_) => false,
+            });
+            let floating = find_floating(&anchors, &removed, &grid.entities_to_neighbours);
+            if keyboard_input_key_code.any_pressed([LOG_KEYCODE_CLUSTER]) {
+                println!("floating {:?}", floating);
+            }
+
+            let mut floating_score_add = 0;
+            for floating_entity in floating.iter() {
+                if let Ok((
+                    floating_entity,
+                    floating_position,
+                    _,
+                    floating_species,
+                    mut grid_ball,
+                    _,
+                    some_projectile_ball,
+                )) = balls_query.get_mut(*floating_entity)
+                {
+                    if grid_ball.is_ready_to_despawn || some_projectile_ball.is_some() {
+                        continue;
+                    }
+                    grid_ball.is_ready_to_despawn = true;
+                    commands.spawn(OutBallBundle::new(
+                        floating_position.0,
+                        *floating_species,
+                        &gameplay_meshes,
+                        &mut materials,
+                        true,
+                    ));
+                    println!("floating entity despawned {:?}", floating_entity);
+                    commands.entity(floating_entity).despawn_recursive();
+                    floating_score_add += FLOATING_BALL_SCORE;
+                }
+            }
+
+            writer_update_cooldown_counter.send(UpdateScoreCounter {
+                score_add: floating_score_add,
+            });
+        }
         cluster_check_cooldown.to_check = HashSet::default();
+        cluster_check_cooldown.to_burn = HashSet::default();
     }
 }
@@ -1,35 +1,47 @@
 use bevy::{
-    prelude::{info, Entity, EventReader, EventWriter, Query, Res, ResMut, Vec2, With, Without},
+    ecs::system::SystemParam,
+    prelude::{
+        info, warn, Commands, DespawnRecursiveExt, Entity, EventReader, EventWriter, Query, Res,
+        ResMut, Vec2, With, Without,
+    },
     time::Time,
 };
 use bevy_xpbd_2d::prelude::{
-    AngularVelocity, CollisionEnded, CollisionStarted, LinearVelocity, Position,
+    AngularVelocity, Collider, CollisionEnded, CollisionStarted, LinearVelocity, Mass, Position,
+    RigidBody, Sleeping, SpatialQuery, SpatialQueryFilter,
 };
 
 use crate::gameplay::{
-    ball::components::{GridBall, GridBallScaleAnimate, ProjectileBall},
-    constants::MIN_PROJECTILE_SNAP_DOT,
-    events::{FindCluster, SnapProjectile},
+    ball::components::{
+        Fireball, GridBall, GridBallScaleAnimate, ProjectileBall, ProjectileState, Species,
+    },
+    constants::{
+        BALL_RADIUS, MIN_PROJECTILE_SNAP_DOT, PROJECTILE_SWEEP_RADIUS, WALL_SIDE_WIDTH,
+        WALL_TOP_HEIGHT,
+    },
+    events::{FindCluster, ProjectileBounced, ProjectilePopped, SnapProjectile, SnapReason},
     grid::{
-        resources::CollisionSnapCooldown,
-        utils::{is_move_reverse, is_move_slow, send_snap_projectile},
+        components::{HexComponent, ProjectileSweep, SettleDetector},
+        resources::{ClusterCheckCooldown, Grid, SettleThresholds},
+        utils::{find_free_hex, send_snap_projectile},
     },
+    physics::layers::Layer,
+    playfield::resources::Playfield,
+    walls::components::{TopWall, WallType},
 };
 
 pub fn check_collision_events(
     mut collision_started_events: EventReader<CollisionStarted>,
     mut collision_ended_events: EventReader<CollisionEnded>,
     mut writer_snap_projectile: EventWriter<SnapProjectile>,
-    mut projectile_query: Query<
-        (
-            Entity,
-            &mut Position,
-            &mut LinearVelocity,
-            &mut AngularVelocity,
-            &mut ProjectileBall,
-        ),
-        With<ProjectileBall>,
-    >,
+    mut projectile_query: Query<(
+        Entity,
+        &Position,
+        &mut LinearVelocity,
+        &mut AngularVelocity,
+        &mut ProjectileBall,
+        &mut SettleDetector,
+    )>,
     balls_query: Query<
         (Entity, &Position),
         (
@@ -38,8 +50,8 @@ pub fn check_collision_events(
             Without<GridBallScaleAnimate>,
         ),
     >,
-    mut collision_snap_cooldown: ResMut<CollisionSnapCooldown>,
     mut writer_find_cluster: EventWriter<FindCluster>,
+    thresholds: Res<SettleThresholds>,
 ) {
     for CollisionStarted(entity_a, entity_b) in collision_started_events.iter() {
         let result_ball_entity_a = balls_query.get(*entity_a);
@@ -65,8 +77,12 @@ pub fn check_collision_events(
                 mut proj_linear_velocity,
                 mut proj_angular_velocity,
                 mut projectile_ball,
+                mut settle_detector,
             )) = result_projectile
             {
+                if settle_detector.is_settled {
+                    continue;
+                }
                 if projectile_ball.snap_vel == Vec2::ZERO {
                     let to_pos = ball_position.0;
                     let from_pos = projectile_position.0;
@@ -74,27 +90,26 @@ pub fn check_collision_events(
                     let vel = proj_linear_velocity.0.normalize();
                     let dot = vel.dot(diff);
                     if dot > MIN_PROJECTILE_SNAP_DOT {
-                        collision_snap_cooldown.start();
+                        settle_detector.touch();
+                        projectile_ball.state = ProjectileState::Settling;
                         // save first touch position
-                        is_move_reverse(&mut projectile_ball, proj_linear_velocity.0);
+                        thresholds.is_reversed(&mut projectile_ball, proj_linear_velocity.0);
                     }
                     proj_linear_velocity.0 = Vec2::ZERO;
                     proj_angular_velocity.0 = 0.0;
                 }
-                let is_move_slow_result = is_move_slow(proj_linear_velocity.0);
-                let is_move_reverse_result =
-                    is_move_reverse(&mut projectile_ball, proj_linear_velocity.0);
-                if is_move_slow_result || is_move_reverse_result {
-                    // if ball turned back
-                    // or ball moves too slow
+                if let Some(reason) =
+                    collision_snap_reason(&thresholds, &mut projectile_ball, proj_linear_velocity.0)
+                {
                     info!(
-                        "Projectile {:?} too slow so snap on collision started",
-                        projectile_entity
+                        "Projectile {:?} {:?} so snap on collision started",
+                        projectile_entity, reason
                     );
                     send_snap_projectile(
-                        collision_snap_cooldown.as_mut(),
+                        settle_detector.as_mut(),
                         &mut writer_snap_projectile,
                         projectile_entity,
+                        reason,
                     );
                 }
             }
@@ -106,23 +121,30 @@ pub fn check_collision_events(
             if result_projectile.is_err() {
                 result_projectile = projectile_query.get_mut(*entity_b);
             }
-            if let Ok((projectile_entity, _, projectile_velocity, _, mut projectile_ball)) =
-                result_projectile
+            if let Ok((
+                projectile_entity,
+                _,
+                projectile_velocity,
+                _,
+                mut projectile_ball,
+                mut settle_detector,
+            )) = result_projectile
             {
-                let is_move_slow_result = is_move_slow(projectile_velocity.0);
-                let is_move_reverse_result =
-                    is_move_reverse(&mut projectile_ball, projectile_velocity.0);
-                if is_move_slow_result || is_move_reverse_result {
-                    // if ball turned back
-                    // or ball moves too slow
+                if settle_detector.is_settled {
+                    continue;
+                }
+                if let Some(reason) =
+                    collision_snap_reason(&thresholds, &mut projectile_ball, projectile_velocity.0)
+                {
                     info!(
-                        "Projectile {:?} too slow so snap on collision ended",
-                        projectile_entity
+                        "Projectile {:?} {:?} so snap on collision ended",
+                        projectile_entity, reason
                     );
                     send_snap_projectile(
-                        collision_snap_cooldown.as_mut(),
+                        settle_detector.as_mut(),
                         &mut writer_snap_projectile,
                         projectile_entity,
+                        reason,
                     );
                 }
             }
@@ -130,32 +152,337 @@ pub fn check_collision_events(
     }
 }
 
-pub fn tick_collision_snap_cooldown_timer(
-    mut collision_snap_cooldown: ResMut<CollisionSnapCooldown>,
+/// ball which turned back or moves too slow after a touch has to be snapped
+fn collision_snap_reason(
+    thresholds: &SettleThresholds,
+    projectile_ball: &mut ProjectileBall,
+    velocity: Vec2,
+) -> Option<SnapReason> {
+    // both checks run, reverse check saves the first touch velocity
+    let is_slow = thresholds.is_slow(velocity);
+    let is_reversed = thresholds.is_reversed(projectile_ball, velocity);
+    match (is_slow, is_reversed) {
+        (true, _) => Some(SnapReason::Slow),
+        (_, true) => Some(SnapReason::Reversed),
+        _ => None,
+    }
+}
+
+pub fn detect_projectile_bounce(
+    mut collision_started_events: EventReader<CollisionStarted>,
+    projectile_query: Query<&ProjectileBall>,
+    wall_query: Query<&WallType>,
+    mut writer_projectile_bounced: EventWriter<ProjectileBounced>,
+) {
+    for CollisionStarted(entity_a, entity_b) in collision_started_events.iter() {
+        let (projectile_entity, wall_entity) = match projectile_query.contains(*entity_a) {
+            true => (*entity_a, *entity_b),
+            false => (*entity_b, *entity_a),
+        };
+        if let (Ok(projectile_ball), Ok(wall_type)) = (
+            projectile_query.get(projectile_entity),
+            wall_query.get(wall_entity),
+        ) {
+            if projectile_ball.state.is_flying() && wall_type.is_side() {
+                writer_projectile_bounced.send(ProjectileBounced { projectile_entity });
+            }
+        }
+    }
+}
+
+/// flying projectile settles when physics puts it to sleep, its kinetic energy stays low or the first touch was too long ago
+pub fn detect_projectile_settle(
     time: Res<Time>,
-    mut projectile_query: Query<
-        (Entity, &mut ProjectileBall, &LinearVelocity),
-        With<ProjectileBall>,
-    >,
+    thresholds: Res<SettleThresholds>,
+    mut projectile_query: Query<(
+        Entity,
+        &LinearVelocity,
+        &Mass,
+        &ProjectileBall,
+        &mut SettleDetector,
+    )>,
+    sleeping_query: Query<(), With<Sleeping>>,
     mut writer_snap_projectile: EventWriter<SnapProjectile>,
 ) {
-    if !collision_snap_cooldown.timer.paused() {
-        collision_snap_cooldown.timer.tick(time.delta());
-        if let Ok((projectile_entity, mut projectile_ball, linear_velocity)) =
-            projectile_query.get_single_mut()
+    let delta = time.delta_seconds();
+    for (projectile_entity, linear_velocity, mass, projectile_ball, mut settle_detector) in
+        projectile_query.iter_mut()
+    {
+        if !projectile_ball.state.is_flying() || settle_detector.is_settled {
+            continue;
+        }
+        let kinetic_energy = 0.5 * mass.0 * linear_velocity.length_squared();
+        settle_detector.low_energy_for = match kinetic_energy < thresholds.low_energy {
+            true => settle_detector.low_energy_for + delta,
+            false => 0.0,
+        };
+        if let Some(touched_for) = settle_detector.touched_for.as_mut() {
+            *touched_for += delta;
+        }
+
+        let reason = if sleeping_query.contains(projectile_entity) {
+            Some(SnapReason::Sleeping)
+        } else if settle_detector.low_energy_for >= thresholds.low_energy_time {
+            Some(SnapReason::Slow)
+        } else if settle_detector
+            .touched_for
+            .is_some_and(|touched_for| touched_for >= thresholds.timeout)
         {
-            if collision_snap_cooldown.is_ready_for_check(|| {
-                println!("is_ready_for_check {}", linear_velocity.0.length());
-                is_move_slow(linear_velocity.0)
-                    || is_move_reverse(&mut projectile_ball, linear_velocity.0)
-            }) {
-                // snap projectile anyway after some time
-                info!("Projectile {:?} cooldown snap", projectile_entity);
-                send_snap_projectile(
-                    collision_snap_cooldown.as_mut(),
-                    &mut writer_snap_projectile,
+            Some(SnapReason::Timeout)
+        } else {
+            None
+        };
+        if let Some(reason) = reason {
+            info!("Projectile {:?} settled {:?}", projectile_entity, reason);
+            send_snap_projectile(
+                settle_detector.as_mut(),
+                &mut writer_snap_projectile,
+                projectile_entity,
+                reason,
+            );
+        }
+    }
+}
+
+/// grid balls and top wall stop projectile on the lattice, side walls only bounce it
+#[derive(SystemParam)]
+pub struct SnapObstacles<'w, 's> {
+    balls_query: Query<'w, 's, (), (With<GridBall>, Without<ProjectileBall>)>,
+    top_wall_query: Query<'w, 's, (), With<TopWall>>,
+}
+
+impl SnapObstacles<'_, '_> {
+    fn contains(&self, entity: Entity) -> bool {
+        self.balls_query.contains(entity) || self.top_wall_query.contains(entity)
+    }
+}
+
+pub fn snap_projectile_to_lattice(
+    mut commands: Commands,
+    mut collision_started_events: EventReader<CollisionStarted>,
+    // fireball has no settle detector, it never snaps
+    mut projectile_query: Query<(
+        Entity,
+        &mut Position,
+        &mut LinearVelocity,
+        &mut AngularVelocity,
+        &mut RigidBody,
+        &mut ProjectileBall,
+        &SettleDetector,
+    )>,
+    snap_obstacles: SnapObstacles,
+    grid: Res<Grid>,
+    mut writer_snap_projectile: EventWriter<SnapProjectile>,
+    mut writer_projectile_popped: EventWriter<ProjectilePopped>,
+) {
+    let mut snapped: Vec<Entity> = vec![];
+    for CollisionStarted(entity_a, entity_b) in collision_started_events.iter() {
+        let (projectile_entity, other_entity) = match projectile_query.contains(*entity_a) {
+            true => (*entity_a, *entity_b),
+            false => (*entity_b, *entity_a),
+        };
+        if snapped.contains(&projectile_entity) || !snap_obstacles.contains(other_entity) {
+            continue;
+        }
+        if let Ok((
+            projectile_entity,
+            mut projectile_position,
+            mut linear_velocity,
+            mut angular_velocity,
+            mut rigid_body,
+            mut projectile_ball,
+            _,
+        )) = projectile_query.get_mut(projectile_entity)
+        {
+            if !projectile_ball.state.is_flying() {
+                continue;
+            }
+            if let Some(hex) = find_free_hex(&grid, projectile_position.0) {
+                projectile_position.0 = grid.layout.hex_to_world_pos(hex);
+                linear_velocity.0 = Vec2::ZERO;
+                angular_velocity.0 = 0.0;
+                *rigid_body = RigidBody::Kinematic;
+                commands
+                    .entity(projectile_entity)
+                    .insert(HexComponent { hex });
+                info!(
+                    "Projectile {:?} snapped to hex {:?}",
+                    projectile_entity, hex
+                );
+                snapped.push(projectile_entity);
+                writer_snap_projectile.send(SnapProjectile {
                     projectile_entity,
+                    reason: SnapReason::Touch,
+                });
+            } else {
+                warn!(
+                    "Projectile {:?} has no free hex to snap at {}, shot ends",
+                    projectile_entity, projectile_position.0
                 );
+                // shot ends like a lost projectile, next one is loaded by end_projectile_shot
+                projectile_ball.state = ProjectileState::Popped;
+                commands.entity(projectile_entity).despawn_recursive();
+                snapped.push(projectile_entity);
+                writer_projectile_popped.send(ProjectilePopped { projectile_entity });
+            }
+        }
+    }
+}
+
+/// fireball burns every grid ball it passes through, except stones
+pub fn burn_grid_balls(
+    mut collision_started_events: EventReader<CollisionStarted>,
+    fireball_query: Query<(), With<Fireball>>,
+    balls_query: Query<&Species, (With<GridBall>, Without<ProjectileBall>)>,
+    mut cluster_check_cooldown: ResMut<ClusterCheckCooldown>,
+    mut writer_find_cluster: EventWriter<FindCluster>,
+) {
+    for CollisionStarted(entity_a, entity_b) in collision_started_events.iter() {
+        let ball_entity = match fireball_query.contains(*entity_a) {
+            true => *entity_b,
+            false if fireball_query.contains(*entity_b) => *entity_a,
+            false => continue,
+        };
+        if balls_query
+            .get(ball_entity)
+            .is_ok_and(|species| *species != Species::Stone)
+        {
+            cluster_check_cooldown.to_burn.insert(ball_entity);
+            writer_find_cluster.send(FindCluster {
+                to_check: ball_entity,
+            });
+        }
+    }
+}
+
+/// sensor fireball has no collision response, side walls reflect it and the top wall puts it out
+pub fn bounce_fireball(
+    mut commands: Commands,
+    mut collision_started_events: EventReader<CollisionStarted>,
+    mut fireball_query: Query<(&mut LinearVelocity, &mut ProjectileBall), With<Fireball>>,
+    wall_query: Query<(&Position, &WallType)>,
+    mut writer_projectile_popped: EventWriter<ProjectilePopped>,
+) {
+    for CollisionStarted(entity_a, entity_b) in collision_started_events.iter() {
+        let (fireball_entity, wall_entity) = match fireball_query.contains(*entity_a) {
+            true => (*entity_a, *entity_b),
+            false => (*entity_b, *entity_a),
+        };
+        let (Ok((mut linear_velocity, mut projectile_ball)), Ok((wall_position, wall_type))) = (
+            fireball_query.get_mut(fireball_entity),
+            wall_query.get(wall_entity),
+        ) else {
+            continue;
+        };
+        if !projectile_ball.state.is_flying() {
+            continue;
+        }
+        if wall_type.is_side() {
+            linear_velocity.x = -linear_velocity.x.abs() * wall_position.x.signum();
+            continue;
+        }
+        println!("Fireball {:?} reached top wall", fireball_entity);
+        // shot ends like a popped projectile, next one is loaded by end_projectile_shot
+        projectile_ball.state = ProjectileState::Popped;
+        commands.entity(fireball_entity).despawn_recursive();
+        writer_projectile_popped.send(ProjectilePopped {
+            projectile_entity: fireball_entity,
+        });
+    }
+}
+
+/// runs after each physics step, a fast projectile can jump over a thin wall or a ball in one step
+pub fn sweep_projectile(
+    mut commands: Commands,
+    spatial_query: SpatialQuery,
+    mut projectile_query: Query<(Entity, &Position, &ProjectileBall, &mut ProjectileSweep)>,
+) {
+    let shape = Collider::ball(PROJECTILE_SWEEP_RADIUS);
+    for (projectile_entity, position, projectile_ball, mut sweep) in projectile_query.iter_mut() {
+        // loaded projectile waits at the bottom, snapped one lost its sweep already
+        if !projectile_ball.state.is_flying() {
+            continue;
+        }
+        let last_position = sweep.last_position.unwrap_or(position.0);
+        sweep.last_position = Some(position.0);
+        let path = position.0 - last_position;
+        let distance = path.length();
+        if distance == 0.0 {
+            continue;
+        }
+        let direction = path / distance;
+        if let Some(hit) = spatial_query.cast_shape(
+            &shape,
+            last_position,
+            0.0,
+            direction,
+            distance,
+            true,
+            SpatialQueryFilter::default()
+                .with_masks([Layer::Walls, Layer::Grid])
+                .without_entities([projectile_entity]),
+        ) {
+            // put full size ball back before the skipped collider, next step collides as usual
+            let back = (hit.time_of_impact - (BALL_RADIUS - PROJECTILE_SWEEP_RADIUS)).max(0.0);
+            let back_position = last_position + direction * back;
+            println!(
+                "Projectile {:?} tunnelled through {:?}, moved back to {}",
+                projectile_entity, hit.entity, back_position
+            );
+            sweep.last_position = Some(back_position);
+            commands
+                .entity(projectile_entity)
+                .insert(Position(back_position));
+        }
+    }
+}
+
+/// projectile found outside the walls is put back inside, the one which left playfield bottom ends the shot
+pub fn recover_lost_projectile(
+    mut commands: Commands,
+    wall_query: Query<(&Position, &WallType), Without<ProjectileBall>>,
+    mut projectile_query: Query<(Entity, &mut Position, &mut ProjectileBall)>,
+    playfield: Res<Playfield>,
+    mut writer_projectile_popped: EventWriter<ProjectilePopped>,
+) {
+    for (projectile_entity, mut position, mut projectile_ball) in projectile_query.iter_mut() {
+        if !projectile_ball.state.is_flying() {
+            continue;
+        }
+        if position.y < playfield.bottom_y(0.0) - BALL_RADIUS {
+            println!(
+                "Projectile {:?} lost below playfield {}, reset",
+                projectile_entity, position.0
+            );
+            // lost shot ends like a popped projectile, next one is loaded by end_projectile_shot
+            projectile_ball.state = ProjectileState::Popped;
+            commands.entity(projectile_entity).despawn_recursive();
+            writer_projectile_popped.send(ProjectilePopped { projectile_entity });
+            continue;
+        }
+        for (wall_position, wall_type) in wall_query.iter() {
+            // keep velocity, so projectile hits the wall it skipped on the next step
+            match wall_type {
+                WallType::Left | WallType::Right => {
+                    let inner_x = wall_position.x.abs() - WALL_SIDE_WIDTH / 2.0;
+                    if position.x.abs() > inner_x {
+                        position.x = (inner_x - BALL_RADIUS) * wall_position.x.signum();
+                        println!(
+                            "Projectile {:?} outside side wall, moved back to {}",
+                            projectile_entity, position.0
+                        );
+                    }
+                }
+                WallType::Top => {
+                    let inner_y = wall_position.y - WALL_TOP_HEIGHT / 2.0;
+                    if position.y > inner_y {
+                        position.y = inner_y - BALL_RADIUS;
+                        println!(
+                            "Projectile {:?} above top wall, moved back to {}",
+                            projectile_entity, position.0
+                        );
+                    }
+                }
             }
         }
     }
@@ -0,0 +1,53 @@
+use bevy::prelude::{
+    Children, Commands, DespawnRecursiveExt, EventReader, EventWriter, Query, Res, With,
+};
+
+use crate::gameplay::{
+    ball::components::{Frozen, IceOverlay},
+    events::{CrackIce, FindCluster},
+    materials::resources::GameplayMaterials,
+};
+
+/// ball without ice layers thaws and is checked for cluster right away
+pub fn crack_ice(
+    mut commands: Commands,
+    mut crack_ice_events: EventReader<CrackIce>,
+    mut frozen_query: Query<(&mut Frozen, &Children)>,
+    overlay_query: Query<(), With<IceOverlay>>,
+    gameplay_materials: Res<GameplayMaterials>,
+    mut writer_find_cluster: EventWriter<FindCluster>,
+) {
+    for CrackIce { ball_entity } in crack_ice_events.iter() {
+        let Ok((mut frozen, children)) = frozen_query.get_mut(*ball_entity) else {
+            continue;
+        };
+        if frozen.layers == 0 {
+            // already thawed by another pop in the same frame
+            continue;
+        }
+        frozen.layers -= 1;
+        let overlays = children
+            .iter()
+            .filter(|child| overlay_query.contains(**child));
+        if frozen.layers > 0 {
+            println!(
+                "Ice cracked {:?} layers left {}",
+                ball_entity, frozen.layers
+            );
+            for overlay_entity in overlays {
+                commands
+                    .entity(*overlay_entity)
+                    .insert(gameplay_materials.ice_for_layers(frozen.layers));
+            }
+            continue;
+        }
+        println!("Ball thawed {:?}", ball_entity);
+        for overlay_entity in overlays {
+            commands.entity(*overlay_entity).despawn_recursive();
+        }
+        commands.entity(*ball_entity).remove::<Frozen>();
+        writer_find_cluster.send(FindCluster {
+            to_check: *ball_entity,
+        });
+    }
+}
@@ -4,7 +4,6 @@ use bevy::{
         With,
     },
     utils::HashSet,
-    window::{PrimaryWindow, Window},
 };
 use bevy_xpbd_2d::prelude::{AngularVelocity, LinearVelocity, Position, RigidBody};
 use hexx::{shapes, Hex};
@@ -13,18 +12,21 @@ use crate::{
     components::AppState,
     gameplay::{
         ball::{
-            components::{GridBall, OutBall},
-            grid_ball_bundle::GridBallBundle,
+            components::{GridBall, OutBall, TopRowBall},
+            grid_ball_bundle::{GridBallAssets, GridBallBundle},
         },
         constants::ROW_HEIGHT,
         events::SpawnRow,
         grid::{
-            resources::{ClusterCheckCooldown, Grid},
+            components::HexComponent,
+            resources::{ClusterCheckCooldown, Grid, GridMode},
             utils::adjust_grid_layout,
         },
         materials::resources::GameplayMaterials,
         meshes::resources::GameplayMeshes,
         panels::resources::SpawnRowsLeft,
+        playfield::resources::Playfield,
+        rng::resources::GameRng,
         walls::components::TopWall,
     },
 };
@@ -34,10 +36,11 @@ pub fn generate_grid(
     gameplay_meshes: Res<GameplayMeshes>,
     gameplay_materials: Res<GameplayMaterials>,
     mut grid: ResMut<Grid>,
-    window_query: Query<&Window, With<PrimaryWindow>>,
+    playfield: Res<Playfield>,
     mut app_state_next_state: ResMut<NextState<AppState>>,
+    mut game_rng: ResMut<GameRng>,
 ) {
-    adjust_grid_layout(&window_query, &mut grid, 0);
+    adjust_grid_layout(&playfield, &mut grid, 0);
     let max_side_x = (grid.init_cols / 2) as i32;
     let min_col = -max_side_x;
     let max_col = max_side_x;
@@ -49,21 +52,67 @@ pub fn generate_grid(
         if (!is_even && offset[0] == max_side_x) || hex.y < grid.last_active_row {
             continue;
         }
+        let (species, ice_layers, is_chameleon) = match grid.level_cells.get(&hex) {
+            Some(Some(species)) => (
+                *species,
+                grid.level_ice.get(&hex).copied().unwrap_or(0),
+                grid.level_chameleons.contains(&hex),
+            ),
+            // empty level cell
+            Some(None) => continue,
+            None => (
+                grid.random_cell_species(&mut game_rng.rng),
+                grid.random_ice_layers(&mut game_rng.rng),
+                grid.random_chameleon(&mut game_rng.rng),
+            ),
+        };
         let is_last_active = hex.y == grid.last_active_row;
         let position = grid.layout.hex_to_world_pos(hex);
 
-        GridBallBundle::spawn(
+        let (entity, _) = GridBallBundle::spawn(
             &mut commands,
             &gameplay_meshes,
             &gameplay_materials,
             grid.total_colors,
             position,
-            is_last_active,
+            is_last_active || grid.mode == GridMode::Classic,
             false,
-            None,
+            Some(species),
             true,
             true,
+            &mut game_rng,
         );
+        if grid.mode == GridMode::Classic {
+            commands.entity(entity).insert(HexComponent { hex });
+        }
+        if is_last_active {
+            commands.entity(entity).insert(TopRowBall);
+        }
+        if ice_layers > 0 && !species.is_special() {
+            GridBallBundle::freeze(
+                &mut commands,
+                entity,
+                ice_layers,
+                &gameplay_meshes,
+                &gameplay_materials,
+            );
+        }
+        if is_chameleon && !species.is_special() {
+            GridBallBundle::camouflage(
+                &mut commands,
+                entity,
+                &gameplay_meshes,
+                &gameplay_materials,
+            );
+        }
+        if grid.level_collectibles.contains(&hex) {
+            GridBallBundle::trap_collectible(
+                &mut commands,
+                entity,
+                &gameplay_meshes,
+                &gameplay_materials,
+            );
+        }
     }
     app_state_next_state.set(AppState::Gameplay);
 }
@@ -88,8 +137,7 @@ pub fn cleanup_grid(
 
 pub fn spawn_new_row(
     mut commands: Commands,
-    gameplay_meshes: Res<GameplayMeshes>,
-    gameplay_materials: Res<GameplayMaterials>,
+    grid_ball_assets: GridBallAssets,
     mut spawn_row_events: EventReader<SpawnRow>,
     mut grid: ResMut<Grid>,
     mut grid_balls_query: Query<
@@ -104,11 +152,16 @@ pub fn spawn_new_row(
     >,
     mut spawn_rows_left: ResMut<SpawnRowsLeft>,
     mut top_wall_query: Query<&mut Position, With<TopWall>>,
+    mut game_rng: ResMut<GameRng>,
 ) {
     if spawn_row_events.is_empty() {
         return;
     }
     spawn_row_events.clear();
+    let GridBallAssets {
+        gameplay_meshes,
+        gameplay_materials,
+    } = grid_ball_assets;
 
     if spawn_rows_left.0 > 0 {
         spawn_rows_left.0 -= 1;
@@ -127,7 +180,10 @@ pub fn spawn_new_row(
                 continue;
             }
             let position = grid.layout.hex_to_world_pos(hex);
-            GridBallBundle::spawn(
+            let species = grid.random_cell_species(&mut game_rng.rng);
+            let ice_layers = grid.random_ice_layers(&mut game_rng.rng);
+            let is_chameleon = grid.random_chameleon(&mut game_rng.rng);
+            let (entity, _) = GridBallBundle::spawn(
                 &mut commands,
                 &gameplay_meshes,
                 &gameplay_materials,
@@ -135,10 +191,36 @@ pub fn spawn_new_row(
                 position,
                 true,
                 false,
-                None,
+                Some(species),
                 true,
                 true,
+                &mut game_rng,
             );
+            if grid.mode == GridMode::Classic {
+                commands.entity(entity).insert(HexComponent { hex });
+            }
+            if ice_layers > 0 && !species.is_special() {
+                GridBallBundle::freeze(
+                    &mut commands,
+                    entity,
+                    ice_layers,
+                    &gameplay_meshes,
+                    &gameplay_materials,
+                );
+            }
+            if is_chameleon && !species.is_special() {
+                GridBallBundle::camouflage(
+                    &mut commands,
+                    entity,
+                    &gameplay_meshes,
+                    &gameplay_materials,
+                );
+            }
+        }
+
+        if grid.mode == GridMode::Classic {
+            // lattice balls stay kinematic, only top row is anchor
+            return;
         }
 
         for (entity, grid_ball, mut velocity, mut angular_velocity, mut rigid_body) in
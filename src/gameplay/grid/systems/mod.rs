@@ -1,6 +1,8 @@
 pub mod animation_systems;
+pub mod chameleon_systems;
 pub mod cluster_systems;
 pub mod collision_systems;
+pub mod ice_systems;
 pub mod lifecycle_systems;
 pub mod magnetic_systems;
 pub mod projectile_systems;
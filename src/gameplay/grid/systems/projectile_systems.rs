@@ -1,50 +1,71 @@
-use bevy::prelude::{Commands, EventReader, EventWriter, Query, ResMut, With};
+use bevy::prelude::{Commands, EventReader, EventWriter, Query, With};
 use bevy_xpbd_2d::prelude::Position;
 
 use crate::gameplay::{
-    ball::components::ProjectileBall,
-    events::{FindCluster, MoveDownTopWall, ProjectileReload, SnapProjectile},
-    panels::resources::TurnCounter,
+    ball::components::{ProjectileBall, ProjectileState},
+    events::{
+        FindCluster, MoveDownTopWall, ProjectilePopped, ProjectileReload, ProjectileSettled,
+        SnapProjectile,
+    },
+    grid::components::{ProjectileSweep, SettleDetector},
 };
 
 pub fn on_snap_projectile(
     mut snap_projectile_events: EventReader<SnapProjectile>,
     mut commands: Commands,
-    mut projectile_reload_writer: EventWriter<ProjectileReload>,
-    mut turn_counter: ResMut<TurnCounter>,
+    mut writer_projectile_settled: EventWriter<ProjectileSettled>,
     mut writer_find_cluster: EventWriter<FindCluster>,
     mut projectile_query: Query<(&mut ProjectileBall, &Position), With<ProjectileBall>>,
-    mut writer_move_down_last_active: EventWriter<MoveDownTopWall>,
 ) {
-    for SnapProjectile { projectile_entity } in snap_projectile_events.iter() {
+    for SnapProjectile {
+        projectile_entity,
+        reason,
+    } in snap_projectile_events.iter()
+    {
         if let Ok((mut projectile_ball, projectile_position)) =
             projectile_query.get_mut(*projectile_entity)
         {
             // projectile ball can be removed by cluster and never snapped
-            if projectile_ball.is_snapped {
+            if !projectile_ball.state.is_flying() {
                 println!(
                     "Skip projectile {:?} already snapped {:?}",
                     projectile_entity, projectile_position.0
                 );
                 continue;
             }
-            projectile_ball.is_snapped = true;
+            projectile_ball.state = ProjectileState::Snapped;
 
             commands
                 .entity(*projectile_entity)
-                .remove::<ProjectileBall>();
+                .remove::<(ProjectileBall, SettleDetector, ProjectileSweep)>();
             println!(
-                "removed ProjectileBall from {:?} position y {}",
-                projectile_entity, projectile_position.y
+                "removed ProjectileBall from {:?} position y {} snap reason {:?}",
+                projectile_entity, projectile_position.y, reason
             );
 
-            turn_counter.0 += 1;
-
-            projectile_reload_writer.send(ProjectileReload);
+            writer_projectile_settled.send(ProjectileSettled {
+                projectile_entity: *projectile_entity,
+                reason: *reason,
+            });
             writer_find_cluster.send(FindCluster {
                 to_check: *projectile_entity,
             });
-            writer_move_down_last_active.send(MoveDownTopWall);
         }
     }
 }
+
+/// every shot ends with settled or popped projectile, the end loads the next one and counts a move
+pub fn end_projectile_shot(
+    mut projectile_settled_events: EventReader<ProjectileSettled>,
+    mut projectile_popped_events: EventReader<ProjectilePopped>,
+    mut projectile_reload_writer: EventWriter<ProjectileReload>,
+    mut writer_move_down_last_active: EventWriter<MoveDownTopWall>,
+) {
+    let ended = projectile_settled_events.len() + projectile_popped_events.len();
+    projectile_settled_events.clear();
+    projectile_popped_events.clear();
+    for _ in 0..ended {
+        projectile_reload_writer.send(ProjectileReload);
+        writer_move_down_last_active.send(MoveDownTopWall);
+    }
+}
@@ -1,45 +1,72 @@
 use bevy::{
-    prelude::{Entity, Input, KeyCode, Query, Res, ResMut, Vec2, With, Without},
-    utils::{HashMap, HashSet},
+    prelude::{Entity, Input, KeyCode, Query, Res, ResMut, With, Without},
+    utils::HashSet,
 };
 use bevy_xpbd_2d::prelude::Position;
 
 use crate::gameplay::{
-    ball::components::{GridBall, ProjectileBall, Species},
+    ball::components::{Frozen, GridBall, ProjectileBall, Species},
     constants::LOG_KEYCODE_RESOURCES,
-    grid::{resources::Grid, utils::build_entities_to_neighbours},
+    grid::{components::HexComponent, resources::Grid},
 };
 
 pub fn update_grid_resources(
     mut grid: ResMut<Grid>,
     balls_query: Query<
-        (Entity, &Position, &Species, &mut GridBall),
+        (
+            Entity,
+            &Position,
+            &Species,
+            &mut GridBall,
+            Option<&HexComponent>,
+        ),
         (With<GridBall>, Without<ProjectileBall>),
     >,
+    frozen_query: Query<(), With<Frozen>>,
     keyboard_input_key_code: Res<Input<KeyCode>>,
 ) {
     let total = balls_query.iter().len();
     let mut entities: HashSet<Entity> = HashSet::with_capacity(total);
-    let mut entities_to_positions: HashMap<Entity, Vec2> = HashMap::with_capacity(total);
-    let mut entities_to_species: HashMap<Entity, Species> = HashMap::with_capacity(total);
+    let mut moved: HashSet<Entity> = HashSet::default();
     let mut active_species: HashSet<Species> = HashSet::with_capacity(grid.total_colors as usize);
+    let mut frozen: HashSet<Entity> = HashSet::default();
     balls_query
         .iter()
-        .for_each(|(entity, position, species, grid_ball)| {
+        .for_each(|(entity, position, species, grid_ball, some_hex)| {
             if !grid_ball.is_ready_to_despawn {
                 entities.insert(entity);
-                entities_to_positions.insert(entity, position.0);
-                entities_to_species.insert(entity, *species);
-                if let None = active_species.get(species) {
+                grid.entities_to_positions.insert(entity, position.0);
+                grid.entities_to_species.insert(entity, *species);
+                let is_moved = match some_hex {
+                    Some(hex_component) => grid.update_hex(entity, hex_component.hex),
+                    None => grid.spatial_index.update(entity, position.0),
+                };
+                if is_moved {
+                    moved.insert(entity);
+                }
+                if frozen_query.contains(entity) {
+                    frozen.insert(entity);
+                }
+                if !species.is_special() && active_species.get(species).is_none() {
                     active_species.insert(*species);
                 }
             }
         });
-    let entities_to_neighbours = build_entities_to_neighbours(&entities, &entities_to_positions);
-    grid.entities_to_positions = entities_to_positions;
-    grid.entities_to_species = entities_to_species;
+    let removed: Vec<Entity> = grid
+        .entities_to_positions
+        .keys()
+        .filter(|entity| !entities.contains(*entity))
+        .copied()
+        .collect();
+    for entity in removed.iter() {
+        let neighbours = grid.remove_ball(*entity);
+        moved.extend(neighbours);
+    }
+    if !moved.is_empty() {
+        grid.refresh_neighbours(&moved);
+    }
     grid.active_species = active_species;
-    grid.entities_to_neighbours = entities_to_neighbours;
+    grid.frozen = frozen;
     if keyboard_input_key_code.any_pressed([LOG_KEYCODE_RESOURCES]) {
         println!(
             "entities_to_positions {:?}\nentities_to_neighbours {:?}",
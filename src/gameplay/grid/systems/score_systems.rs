@@ -14,11 +14,12 @@ pub fn update_score_counter(
     mut update_cooldown_counter_events: EventReader<UpdateScoreCounter>,
     mut score_counter: ResMut<ScoreCounter>,
 ) {
-    if let Some(UpdateScoreCounter { score_add }) = update_cooldown_counter_events.iter().next() {
-        if *score_add > 0 {
-            pkv_play_score_audio(&mut commands, &audio_assets, &pkv);
-            score_counter.0 += score_add;
-        }
+    let mut total_score_add = 0;
+    for UpdateScoreCounter { score_add } in update_cooldown_counter_events.iter() {
+        total_score_add += score_add;
+    }
+    if total_score_add > 0 {
+        pkv_play_score_audio(&mut commands, &audio_assets, &pkv);
+        score_counter.0 += total_score_add;
     }
-    update_cooldown_counter_events.clear();
 }
@@ -1,57 +1,105 @@
 use bevy::{
-    prelude::{default, ChildBuilder, Color, Entity, EventWriter, Query, Vec2, With},
+    prelude::{default, ChildBuilder, Color, Entity, EventWriter, Res, Vec2},
     text::{Text, Text2dBounds, Text2dBundle, TextSection, TextStyle},
     utils::{HashMap, HashSet},
-    window::{PrimaryWindow, Window},
 };
 use hexx::Hex;
 
 use crate::gameplay::{
-    ball::components::{ProjectileBall, Species},
+    ball::components::Species,
     constants::{
-        BALL_DIAMETER, EMPTY_PLAYGROUND_HEIGHT, MIN_PROJECTILE_REVERSE_VELOCITY,
-        MIN_PROJECTILE_SNAP_VELOCITY, NEIGHBOUR_POSITION_TOLERANCE, PROJECTILE_SPAWN_BOTTOM,
-        ROW_HEIGHT,
+        BALL_DIAMETER, BOMB_BLAST_RINGS, EMPTY_PLAYGROUND_HEIGHT, FREE_HEX_SEARCH_RANGE,
+        MIN_CLUSTER_SIZE, NEIGHBOUR_POSITION_TOLERANCE, PROJECTILE_SPAWN_BOTTOM, ROW_HEIGHT,
     },
-    events::SnapProjectile,
+    events::{SnapProjectile, SnapReason},
+    playfield::resources::Playfield,
 };
 
-use super::resources::{CollisionSnapCooldown, Grid};
-
-pub fn build_entities_to_neighbours<'a>(
-    entities: &HashSet<Entity>,
-    entities_to_positions: &HashMap<Entity, Vec2>,
-) -> HashMap<Entity, Vec<(Entity, f32)>> {
-    let mut entities_to_neighbours: HashMap<Entity, Vec<(Entity, f32)>> =
-        HashMap::with_capacity(entities.len());
-    for entity in entities.iter() {
-        for neighbour_entity in entities.iter() {
-            if neighbour_entity != entity {
-                if let Some(entity_position) = entities_to_positions.get(entity) {
-                    let entry = entities_to_neighbours
-                        .entry(*entity)
-                        .or_insert(Vec::with_capacity(entities_to_positions.len()));
-
-                    if let Some(neighbour_position) = entities_to_positions.get(neighbour_entity) {
-                        entry.push((
-                            *neighbour_entity,
-                            (*entity_position).distance(*neighbour_position),
-                        ));
-                    }
-                }
-            }
-        }
+use super::{
+    components::SettleDetector,
+    resources::{Grid, GridMode},
+};
+
+pub fn find_cluster(
+    start_from: Entity,
+    entities_to_neighbours: &HashMap<Entity, Vec<(Entity, f32)>>,
+    entities_to_species: &HashMap<Entity, Species>,
+    frozen: &HashSet<Entity>,
+) -> (HashSet<Entity>, HashSet<Entity>) {
+    // frozen ball is skipped until its ice is cracked
+    if frozen.contains(&start_from) {
+        let single = HashSet::from_iter([start_from]);
+        return (single.clone(), single);
     }
-    for (_, neighbours) in entities_to_neighbours.iter_mut() {
-        neighbours.sort_by(|(_, distance_a), (_, distance_b)| distance_a.total_cmp(distance_b));
+    let start_neighbours = match entities_to_neighbours.get(&start_from) {
+        Some(neighbours) => neighbours.as_slice(),
+        None => &[],
+    };
+    find_cluster_from(
+        start_from,
+        entities_to_species.get(&start_from),
+        start_neighbours,
+        entities_to_neighbours,
+        entities_to_species,
+        frozen,
+    )
+}
+
+/// start species and neighbours are passed on the side, so start ball can be missing in grid data
+fn find_cluster_from(
+    start_from: Entity,
+    start_species: Option<&Species>,
+    start_neighbours: &[(Entity, f32)],
+    entities_to_neighbours: &HashMap<Entity, Vec<(Entity, f32)>>,
+    entities_to_species: &HashMap<Entity, Species>,
+    frozen: &HashSet<Entity>,
+) -> (HashSet<Entity>, HashSet<Entity>) {
+    let flood_cluster = |cluster_species: &Species| {
+        flood_cluster(
+            start_from,
+            cluster_species,
+            start_neighbours,
+            entities_to_neighbours,
+            entities_to_species,
+            frozen,
+        )
+    };
+    match start_species {
+        Some(Species::Rainbow) => {
+            // wildcard joins whichever neighbour colour forms the largest group
+            let mut neighbour_species: Vec<Species> = start_neighbours
+                .iter()
+                .take_while(|(_, distance)| *distance <= NEIGHBOUR_POSITION_TOLERANCE)
+                .filter(|(neighbour, _)| !frozen.contains(neighbour))
+                .filter_map(|(neighbour, _)| entities_to_species.get(neighbour))
+                .filter(|species| !species.is_special())
+                .copied()
+                .collect();
+            // keep the same pick on ties for the same seed
+            neighbour_species.sort_by_key(|species| *species as u8);
+            neighbour_species.dedup();
+            neighbour_species
+                .iter()
+                .map(flood_cluster)
+                .max_by_key(|(cluster, _)| cluster.len())
+                .unwrap_or_else(|| flood_cluster(&Species::Rainbow))
+        }
+        Some(species) if *species != Species::Stone => flood_cluster(species),
+        // stone is never part of a cluster
+        _ => {
+            let single = HashSet::from_iter([start_from]);
+            (single.clone(), single)
+        }
     }
-    entities_to_neighbours
 }
 
-pub fn find_cluster(
+fn flood_cluster(
     start_from: Entity,
+    cluster_species: &Species,
+    start_neighbours: &[(Entity, f32)],
     entities_to_neighbours: &HashMap<Entity, Vec<(Entity, f32)>>,
     entities_to_species: &HashMap<Entity, Species>,
+    frozen: &HashSet<Entity>,
 ) -> (HashSet<Entity>, HashSet<Entity>) {
     let mut to_process: Vec<&Entity> = vec![&start_from];
     let mut processed: HashSet<Entity> = HashSet::default();
@@ -64,22 +112,24 @@ pub fn find_cluster(
         }
         cluster.insert(*current);
         processed.insert(*current);
-        if let Some(current_species) = entities_to_species.get(current) {
-            if let Some(neighbours) = entities_to_neighbours.get(current) {
-                for (neighbour, distance) in neighbours.iter() {
-                    // if neighbour is still in the grid and wasn't removed by cluster
-                    if processed.contains(neighbour) {
+        let some_neighbours = match *current == start_from {
+            true => Some(start_neighbours),
+            false => entities_to_neighbours.get(current).map(Vec::as_slice),
+        };
+        if let Some(neighbours) = some_neighbours {
+            for (neighbour, distance) in neighbours.iter() {
+                // if neighbour is still in the grid and wasn't removed by cluster
+                if processed.contains(neighbour) || frozen.contains(neighbour) {
+                    continue;
+                }
+                if *distance > NEIGHBOUR_POSITION_TOLERANCE {
+                    break;
+                }
+                if let Some(neighbour_species) = entities_to_species.get(neighbour) {
+                    if !neighbour_species.matches(cluster_species) {
                         continue;
                     }
-                    if *distance > NEIGHBOUR_POSITION_TOLERANCE {
-                        break;
-                    }
-                    if let Some(neighbour_species) = entities_to_species.get(neighbour) {
-                        if current_species != neighbour_species {
-                            continue;
-                        }
-                        to_process.push(neighbour);
-                    }
+                    to_process.push(neighbour);
                 }
             }
         }
@@ -87,41 +137,155 @@ pub fn find_cluster(
     (cluster, processed)
 }
 
-pub fn adjust_grid_layout(
-    window_query: &Query<&Window, With<PrimaryWindow>>,
-    grid: &mut Grid,
-    move_count: u32,
-) {
-    let window = window_query.single();
-    let spawn_projectile_world_y =
-        -(window.height() - PROJECTILE_SPAWN_BOTTOM - window.height() / 2.0);
-    let init_layout_y = spawn_projectile_world_y + EMPTY_PLAYGROUND_HEIGHT;
-    let move_layout_y = move_count as f32 * ROW_HEIGHT;
-    grid.layout.origin.y = init_layout_y - move_layout_y;
-    println!("Adjust Grid Layout y {}", grid.layout.origin.y);
+/// balls within bomb blast rings around the bomb, colour does not matter, stones stay,
+/// lattice rings are hex distance and magnetic rings are neighbour hops
+pub fn find_blast(
+    grid: &Grid,
+    bomb: Entity,
+    position: Vec2,
+    bomb_neighbours: &[(Entity, f32)],
+) -> HashSet<Entity> {
+    let mut blast: HashSet<Entity> = HashSet::from_iter([bomb]);
+    match grid.mode {
+        GridMode::Classic => {
+            blast.extend(
+                grid.layout
+                    .world_pos_to_hex(position)
+                    .range(BOMB_BLAST_RINGS)
+                    .filter_map(|hex| grid.hexes_to_entities.get(&hex)),
+            );
+        }
+        GridMode::Magnetic => {
+            let mut ring: Vec<Entity> = vec![bomb];
+            for _ in 0..BOMB_BLAST_RINGS {
+                let mut next_ring: Vec<Entity> = vec![];
+                for entity in ring.iter() {
+                    let neighbours = match *entity == bomb {
+                        true => bomb_neighbours,
+                        false => grid
+                            .entities_to_neighbours
+                            .get(entity)
+                            .map(Vec::as_slice)
+                            .unwrap_or_default(),
+                    };
+                    for (neighbour, distance) in neighbours.iter() {
+                        if *distance > NEIGHBOUR_POSITION_TOLERANCE {
+                            break;
+                        }
+                        if blast.insert(*neighbour) {
+                            next_ring.push(*neighbour);
+                        }
+                    }
+                }
+                ring = next_ring;
+            }
+        }
+    }
+    blast.retain(|entity| grid.entities_to_species.get(entity) != Some(&Species::Stone));
+    blast
 }
 
-pub fn is_move_slow(velocity: Vec2) -> bool {
-    velocity.length() <= MIN_PROJECTILE_SNAP_VELOCITY
-}
+pub fn find_floating(
+    anchors: &HashSet<Entity>,
+    removed: &HashSet<Entity>,
+    entities_to_neighbours: &HashMap<Entity, Vec<(Entity, f32)>>,
+) -> HashSet<Entity> {
+    // walk from the balls attached to the ceiling, everything unreachable is floating
+    let mut to_process: Vec<&Entity> = anchors
+        .iter()
+        .filter(|anchor| !removed.contains(*anchor))
+        .collect();
+    let mut connected: HashSet<Entity> = HashSet::default();
 
-/// detect that projectile after snap is moving into the same clockwise/counterclockwise direction around snap grid ball
-pub fn is_move_reverse(projectile_ball: &mut ProjectileBall, projectile_velocity: Vec2) -> bool {
-    if projectile_ball.snap_vel == Vec2::ZERO {
-        projectile_ball.snap_vel = projectile_velocity.normalize();
-    } else {
-        if projectile_velocity.y < MIN_PROJECTILE_REVERSE_VELOCITY {
-            return true;
+    while let Some(current) = to_process.pop() {
+        if connected.contains(current) {
+            continue;
         }
-        let dot = projectile_ball
-            .snap_vel
-            .dot(projectile_velocity.normalize());
-        println!("dot {}", dot);
-        if dot < 0.0 {
-            return true;
+        connected.insert(*current);
+        if let Some(neighbours) = entities_to_neighbours.get(current) {
+            for (neighbour, distance) in neighbours.iter() {
+                if *distance > NEIGHBOUR_POSITION_TOLERANCE {
+                    break;
+                }
+                if removed.contains(neighbour) || connected.contains(neighbour) {
+                    continue;
+                }
+                to_process.push(neighbour);
+            }
         }
     }
-    false
+    entities_to_neighbours
+        .keys()
+        .filter(|entity| !connected.contains(*entity) && !removed.contains(*entity))
+        .copied()
+        .collect()
+}
+
+/// balls attached to the ceiling, lattice balls by top row and others by kinematic body
+pub fn find_anchors(grid: &Grid, is_kinematic: impl Fn(Entity) -> bool) -> HashSet<Entity> {
+    grid.entities_to_neighbours
+        .keys()
+        .filter(|entity| match grid.entities_to_hexes.get(*entity) {
+            // all lattice balls are kinematic, top row holds the rest
+            Some(hex) => hex.y == grid.last_active_row,
+            None => is_kinematic(**entity),
+        })
+        .copied()
+        .collect()
+}
+
+/// what-if query, balls which would be popped by projectile resting at position
+/// from predict_rest_position, projectile neighbours are found on the side so grid data is untouched
+pub fn predict_popped(
+    grid: &Grid,
+    anchors: &HashSet<Entity>,
+    position: Vec2,
+    species: Species,
+) -> HashSet<Entity> {
+    // placeholder stands for the projectile, no ball in grid data links back to it
+    let projectile = Entity::PLACEHOLDER;
+    let projectile_neighbours: Vec<(Entity, f32)> = match grid.mode {
+        GridMode::Classic => grid
+            .layout
+            .world_pos_to_hex(position)
+            .all_neighbors()
+            .iter()
+            .filter_map(|neighbour_hex| grid.hexes_to_entities.get(neighbour_hex))
+            .map(|neighbour| (*neighbour, BALL_DIAMETER))
+            .collect(),
+        GridMode::Magnetic => grid.spatial_index.find_near(position),
+    };
+
+    let mut popped = match species {
+        Species::Bomb => find_blast(grid, projectile, position, &projectile_neighbours),
+        _ => {
+            let (cluster, _) = find_cluster_from(
+                projectile,
+                Some(&species),
+                &projectile_neighbours,
+                &grid.entities_to_neighbours,
+                &grid.entities_to_species,
+                &grid.frozen,
+            );
+            if cluster.len() < MIN_CLUSTER_SIZE {
+                return HashSet::default();
+            }
+            cluster
+        }
+    };
+    // projectile is removed with the popped balls, so it never holds others
+    let floating = find_floating(anchors, &popped, &grid.entities_to_neighbours);
+    popped.extend(floating);
+    popped.remove(&projectile);
+    popped
+}
+
+pub fn adjust_grid_layout(playfield: &Playfield, grid: &mut Grid, move_count: u32) {
+    let spawn_projectile_world_y = playfield.bottom_y(PROJECTILE_SPAWN_BOTTOM);
+    let init_layout_y = spawn_projectile_world_y + EMPTY_PLAYGROUND_HEIGHT;
+    let move_layout_y = move_count as f32 * ROW_HEIGHT;
+    grid.layout.origin.y = init_layout_y - move_layout_y;
+    println!("Adjust Grid Layout y {}", grid.layout.origin.y);
 }
 
 pub fn build_ball_text(parent: &mut ChildBuilder<'_, '_, '_>, some_hex: Option<Hex>) {
@@ -154,10 +318,69 @@ pub fn build_ball_text(parent: &mut ChildBuilder<'_, '_, '_>, some_hex: Option<H
 }
 
 pub fn send_snap_projectile(
-    collision_snap_cooldown: &mut CollisionSnapCooldown,
+    settle_detector: &mut SettleDetector,
     writer_snap_projectile: &mut EventWriter<SnapProjectile>,
     projectile_entity: Entity,
+    reason: SnapReason,
 ) {
-    collision_snap_cooldown.stop();
-    writer_snap_projectile.send(SnapProjectile { projectile_entity });
+    settle_detector.is_settled = true;
+    writer_snap_projectile.send(SnapProjectile {
+        projectile_entity,
+        reason,
+    });
+}
+
+pub fn is_classic_mode(grid: Res<Grid>) -> bool {
+    grid.mode == GridMode::Classic
+}
+
+pub fn is_magnetic_mode(grid: Res<Grid>) -> bool {
+    grid.mode == GridMode::Magnetic
+}
+
+/// nearest to position hex inside grid bounds which is not occupied by other ball
+pub fn find_free_hex(grid: &Grid, position: Vec2) -> Option<Hex> {
+    let center = grid.layout.world_pos_to_hex(position);
+    // full neighbourhood widens the search, grid bounds are open downwards so it ends quickly
+    let max_range = FREE_HEX_SEARCH_RANGE + grid.init_cols as u32 + grid.total_rows as u32;
+    (FREE_HEX_SEARCH_RANGE..=max_range).find_map(|range| {
+        center
+            .range(range)
+            .filter(|hex| !grid.hexes_to_entities.contains_key(hex) && grid.is_hex_in_bounds(*hex))
+            .min_by(|hex_a, hex_b| {
+                let distance_a = grid.layout.hex_to_world_pos(*hex_a).distance(position);
+                let distance_b = grid.layout.hex_to_world_pos(*hex_b).distance(position);
+                distance_a.total_cmp(&distance_b)
+            })
+    })
+}
+
+/// where projectile touching the grid at position comes to rest,
+/// lattice snaps it into a free hex, magnets keep it next to the nearest ball
+pub fn predict_rest_position(grid: &Grid, position: Vec2) -> Vec2 {
+    match grid.mode {
+        GridMode::Classic => match find_free_hex(grid, position) {
+            Some(hex) => grid.layout.hex_to_world_pos(hex),
+            None => position,
+        },
+        GridMode::Magnetic => {
+            let nearest = grid
+                .spatial_index
+                .find_near(position)
+                .iter()
+                .filter_map(|(entity, _)| grid.entities_to_positions.get(entity))
+                .min_by(|ball_a, ball_b| {
+                    ball_a
+                        .distance(position)
+                        .total_cmp(&ball_b.distance(position))
+                })
+                .copied();
+            match nearest {
+                Some(ball_position) if ball_position.distance(position) < BALL_DIAMETER => {
+                    ball_position + (position - ball_position).normalize_or_zero() * BALL_DIAMETER
+                }
+                _ => position,
+            }
+        }
+    }
 }
@@ -0,0 +1,10 @@
+use bevy::prelude::Component;
+
+use crate::ui::resources::ColorType;
+
+/// starts gameplay with level, random grid for None
+#[derive(Component)]
+pub struct StartLevelButton {
+    pub color_type: ColorType,
+    pub some_level: Option<usize>,
+}
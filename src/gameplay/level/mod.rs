@@ -0,0 +1,37 @@
+use bevy::prelude::{in_state, App, IntoSystemConfigs, OnEnter, Plugin, Update};
+
+use crate::components::AppState;
+
+use self::{
+    resources::{Objectives, SelectedLevel},
+    systems::{apply_level, complete_level, update_objective_progress},
+    utils::read_level_arg,
+};
+
+use super::{panels::systems::setup_resources, systems::check_game_win};
+
+pub mod components;
+pub mod resources;
+pub mod systems;
+pub mod utils;
+
+pub struct LevelPlugin;
+
+impl Plugin for LevelPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(SelectedLevel(read_level_arg()))
+            .init_resource::<Objectives>()
+            .add_systems(
+                OnEnter(AppState::GameplayInit),
+                // level overrides grid settings
+                apply_level.after(setup_resources),
+            )
+            .add_systems(
+                Update,
+                update_objective_progress
+                    .before(check_game_win)
+                    .run_if(in_state(AppState::Gameplay)),
+            )
+            .add_systems(OnEnter(AppState::GameWin), complete_level);
+    }
+}
@@ -0,0 +1,38 @@
+use bevy::{prelude::Resource, utils::HashMap};
+
+use crate::{gameplay::ball::components::Species, loading::level_assets::LevelObjective};
+
+/// index in LevelAssets, random grid is generated when nothing is selected
+#[derive(Resource, Default)]
+pub struct SelectedLevel(pub Option<usize>);
+
+/// campaign level which was just won
+#[derive(Resource)]
+pub struct LevelResult {
+    pub level: usize,
+    pub stars: u8,
+}
+
+/// counted during the game to check level objective
+#[derive(Default, Debug)]
+pub struct ObjectiveProgress {
+    pub score: u32,
+    pub popped: HashMap<Species, u32>,
+    pub is_board_cleared: bool,
+    pub is_top_row_cleared: bool,
+    pub collectibles_total: u32,
+    pub collectibles_left: u32,
+}
+
+impl ObjectiveProgress {
+    pub fn popped(&self, species: &Species) -> u32 {
+        self.popped.get(species).copied().unwrap_or(0)
+    }
+}
+
+/// objective of the current game, random grid has to be cleared
+#[derive(Resource, Default)]
+pub struct Objectives {
+    pub objective: LevelObjective,
+    pub progress: ObjectiveProgress,
+}
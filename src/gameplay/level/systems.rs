@@ -0,0 +1,225 @@
+use bevy::{
+    asset::Assets,
+    prelude::{Added, Changed, Commands, Entity, NextState, Query, Res, ResMut, With, Without},
+    ui::{BackgroundColor, Interaction},
+};
+use bevy_pkv::PkvStore;
+use hexx::Hex;
+
+use crate::{
+    components::AppState,
+    gameplay::{
+        ball::components::{
+            BlastOutBall, GridBall, MatchedOutBall, OutBall, OutBallAnimation, ProjectileBall,
+            Species, TopRowBall,
+        },
+        grid::resources::{CooldownMoveCounter, Grid},
+        panels::resources::{CollectibleCounter, ScoreCounter, SpawnRowsLeft, TurnCounter},
+    },
+    loading::level_assets::{LevelAsset, LevelAssets, LevelObjective},
+    ui::{
+        resources::{PointerCooldown, UIMenuButtonColors},
+        utils::button_utils::button_color_by_interaction,
+    },
+};
+
+use super::{
+    components::StartLevelButton,
+    resources::{LevelResult, Objectives, SelectedLevel},
+    utils::save_level_stars,
+};
+
+pub fn apply_level(
+    mut commands: Commands,
+    mut grid: ResMut<Grid>,
+    mut spawn_rows_left: ResMut<SpawnRowsLeft>,
+    selected_level: Res<SelectedLevel>,
+    level_assets: Res<LevelAssets>,
+    levels: Res<Assets<LevelAsset>>,
+    mut objectives: ResMut<Objectives>,
+) {
+    *objectives = Objectives::default();
+    grid.colors.clear();
+    grid.level_cells.clear();
+    grid.level_ice.clear();
+    grid.level_chameleons.clear();
+    grid.level_collectibles.clear();
+    let level_index = match selected_level.0 {
+        Some(level_index) => level_index,
+        None => return,
+    };
+    let level = match level_assets
+        .levels
+        .get(level_index)
+        .and_then(|handle| levels.get(handle))
+    {
+        Some(level) => level,
+        None => {
+            println!(
+                "Level {} is not loaded, random grid is used",
+                level_index + 1
+            );
+            return;
+        }
+    };
+    println!("Level {} {}", level_index + 1, level.name);
+
+    commands.insert_resource(CooldownMoveCounter::init(level.move_down_after));
+    grid.init_cols = level.cols;
+    grid.init_rows = level.rows;
+    grid.total_rows = level.total_rows.max(level.rows);
+    // stones and special projectiles are not level colours
+    grid.colors = level
+        .colors
+        .iter()
+        .filter(|species| !species.is_special())
+        .copied()
+        .collect();
+    grid.total_colors = grid.colors.len() as u8;
+    grid.stone_chance = level.stone_chance;
+    grid.ice_chance = level.ice_chance;
+    grid.chameleon_chance = level.chameleon_chance;
+    let offset_mode = grid.offset_mode;
+    grid.level_cells = level
+        .cells
+        .iter()
+        .map(|cell| {
+            (
+                Hex::from_offset_coordinates([cell.x, cell.y], offset_mode),
+                cell.species,
+            )
+        })
+        .collect();
+    grid.level_ice = level
+        .cells
+        .iter()
+        .filter(|cell| cell.ice > 0)
+        .map(|cell| {
+            (
+                Hex::from_offset_coordinates([cell.x, cell.y], offset_mode),
+                cell.ice,
+            )
+        })
+        .collect();
+    grid.level_chameleons = level
+        .cells
+        .iter()
+        .filter(|cell| cell.chameleon)
+        .map(|cell| Hex::from_offset_coordinates([cell.x, cell.y], offset_mode))
+        .collect();
+    grid.level_collectibles = level
+        .cells
+        .iter()
+        .filter(|cell| cell.collectible && cell.species.is_some())
+        .map(|cell| Hex::from_offset_coordinates([cell.x, cell.y], offset_mode))
+        .collect();
+    let total_collectibles = grid.level_collectibles.len() as u32;
+    commands.insert_resource(CollectibleCounter {
+        total: total_collectibles,
+        left: total_collectibles,
+    });
+    objectives.objective = match &level.objective {
+        Some(objective) => objective.clone(),
+        None if !grid.level_collectibles.is_empty() => LevelObjective::Any(vec![
+            LevelObjective::ClearBoard,
+            LevelObjective::FreeCollectibles,
+        ]),
+        None => LevelObjective::ClearBoard,
+    };
+    grid.calc_last_active_row();
+
+    spawn_rows_left.0 = (grid.total_rows - grid.init_rows) as u32;
+}
+
+/// objective progress is collected from counters and grid balls left
+pub fn update_objective_progress(
+    mut objectives: ResMut<Objectives>,
+    score_counter: Res<ScoreCounter>,
+    collectible_counter: Res<CollectibleCounter>,
+    balls_query: Query<&Species, (With<GridBall>, Without<ProjectileBall>)>,
+    top_row_query: Query<&Species, With<TopRowBall>>,
+    out_balls_query: Query<(Entity, &OutBall, &Species, Option<&BlastOutBall>), Added<OutBall>>,
+    matched_out_balls_query: Query<&MatchedOutBall>,
+) {
+    let progress = &mut objectives.progress;
+    progress.score = score_counter.0;
+    progress.collectibles_total = collectible_counter.total;
+    progress.collectibles_left = collectible_counter.left;
+    // stones held by the ceiling can not be removed, they do not count
+    progress.is_board_cleared = balls_query.iter().all(|species| *species == Species::Stone);
+    progress.is_top_row_cleared = top_row_query
+        .iter()
+        .all(|species| *species == Species::Stone);
+    for (entity, out_ball, species, blast) in out_balls_query.iter() {
+        if out_ball.animation_type == OutBallAnimation::FixedCluster && blast.is_none() {
+            let species = matched_out_balls_query
+                .get(entity)
+                .map_or(*species, |MatchedOutBall(species)| *species);
+            *progress.popped.entry(species).or_insert(0) += 1;
+        }
+    }
+}
+
+pub fn complete_level(
+    mut commands: Commands,
+    mut selected_level: ResMut<SelectedLevel>,
+    level_assets: Res<LevelAssets>,
+    levels: Res<Assets<LevelAsset>>,
+    score_counter: Res<ScoreCounter>,
+    turn_counter: Res<TurnCounter>,
+    mut pkv: ResMut<PkvStore>,
+) {
+    commands.remove_resource::<LevelResult>();
+    if let Some(level_index) = selected_level.0 {
+        if let Some(level) = level_assets
+            .levels
+            .get(level_index)
+            .and_then(|handle| levels.get(handle))
+        {
+            let stars = level.stars.count(score_counter.0, turn_counter.0);
+            println!(
+                "Level {} won with {} stars, score {} turns {}",
+                level_index + 1,
+                stars,
+                score_counter.0,
+                turn_counter.0
+            );
+            save_level_stars(&mut pkv, level_index, stars);
+            commands.insert_resource(LevelResult {
+                level: level_index,
+                stars,
+            });
+            // next game continues campaign, random grid after the last level
+            selected_level.0 = match level_index + 1 < level_assets.levels.len() {
+                true => Some(level_index + 1),
+                false => None,
+            };
+        }
+    }
+}
+
+pub fn interact_with_start_level_button(
+    button_colors: Res<UIMenuButtonColors>,
+    mut app_state_next_state: ResMut<NextState<AppState>>,
+    mut selected_level: ResMut<SelectedLevel>,
+    mut interaction_query: Query<
+        (&Interaction, &mut BackgroundColor, &StartLevelButton),
+        Changed<Interaction>,
+    >,
+    mut pointer_cooldown: ResMut<PointerCooldown>,
+) {
+    for (interaction, mut background_color, start_level_button) in &mut interaction_query {
+        *background_color = button_color_by_interaction(
+            false,
+            &button_colors,
+            &start_level_button.color_type,
+            interaction,
+        )
+        .into();
+        if *interaction == Interaction::Pressed {
+            pointer_cooldown.started = true;
+            selected_level.0 = start_level_button.some_level;
+            app_state_next_state.set(AppState::GameplayInit);
+        }
+    }
+}
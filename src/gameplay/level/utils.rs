@@ -0,0 +1,39 @@
+use bevy_pkv::PkvStore;
+
+use crate::{
+    constants::{LEVEL_ARG, LEVEL_STARS_KEY},
+    utils::read_arg,
+};
+
+/// level number starts from 1
+pub fn read_level_arg() -> Option<usize> {
+    read_arg::<usize>(LEVEL_ARG).and_then(|level| level.checked_sub(1))
+}
+
+pub fn read_level_stars(pkv: &PkvStore, level: usize) -> u8 {
+    match pkv.get::<String>(&format!("{}_{}", LEVEL_STARS_KEY, level)) {
+        Ok(stars) => stars.parse::<u8>().unwrap_or(0),
+        Err(_) => 0,
+    }
+}
+
+/// keeps the best result
+pub fn save_level_stars(pkv: &mut PkvStore, level: usize, stars: u8) {
+    if stars > read_level_stars(pkv, level) {
+        pkv.set_string(format!("{}_{}", LEVEL_STARS_KEY, level), &stars.to_string())
+            .expect("failed to save level stars");
+    }
+}
+
+/// the first level is always open, others are unlocked by winning the previous one
+pub fn is_level_unlocked(pkv: &PkvStore, level: usize) -> bool {
+    level == 0 || read_level_stars(pkv, level - 1) > 0
+}
+
+/// campaign continues from the furthest unlocked level
+pub fn find_campaign_level(pkv: &PkvStore, total_levels: usize) -> usize {
+    (0..total_levels)
+        .filter(|level| is_level_unlocked(pkv, *level))
+        .last()
+        .unwrap_or(0)
+}
@@ -1,4 +1,7 @@
-use bevy::prelude::{Assets, Color, ColorMaterial, Handle, ResMut, Resource};
+use bevy::{
+    prelude::{Assets, Color, ColorMaterial, Handle, Image, ResMut, Resource},
+    render::render_resource::{Extent3d, TextureDimension, TextureFormat},
+};
 
 use crate::gameplay::ball::components::Species;
 
@@ -11,10 +14,18 @@ pub struct GameplayMaterials {
     pub yellow: Handle<ColorMaterial>,
     pub white: Handle<ColorMaterial>,
     pub purple: Handle<ColorMaterial>,
+    pub bomb: Handle<ColorMaterial>,
+    pub rainbow: Handle<ColorMaterial>,
+    pub fireball: Handle<ColorMaterial>,
+    pub stone: Handle<ColorMaterial>,
+    pub ice: Handle<ColorMaterial>,
+    pub thick_ice: Handle<ColorMaterial>,
+    pub collectible: Handle<ColorMaterial>,
     pub side_wall: Handle<ColorMaterial>,
     pub game_over_line: Handle<ColorMaterial>,
     pub aim_target: Handle<ColorMaterial>,
     pub aim_line: Handle<ColorMaterial>,
+    pub pop_hint: Handle<ColorMaterial>,
 }
 
 impl Default for GameplayMaterials {
@@ -27,16 +38,27 @@ impl Default for GameplayMaterials {
             yellow: Handle::default(),
             white: Handle::default(),
             purple: Handle::default(),
+            bomb: Handle::default(),
+            rainbow: Handle::default(),
+            fireball: Handle::default(),
+            stone: Handle::default(),
+            ice: Handle::default(),
+            thick_ice: Handle::default(),
+            collectible: Handle::default(),
             side_wall: Handle::default(),
             game_over_line: Handle::default(),
             aim_target: Handle::default(),
             aim_line: Handle::default(),
+            pop_hint: Handle::default(),
         }
     }
 }
 
 impl GameplayMaterials {
-    pub fn new(materials: &mut ResMut<Assets<ColorMaterial>>) -> Self {
+    pub fn new(
+        materials: &mut ResMut<Assets<ColorMaterial>>,
+        images: &mut ResMut<Assets<Image>>,
+    ) -> Self {
         Self {
             red: materials.add(Species::Red.into()),
             blue: materials.add(Species::Blue.into()),
@@ -45,10 +67,21 @@ impl GameplayMaterials {
             yellow: materials.add(Species::Yellow.into()),
             white: materials.add(Species::White.into()),
             purple: materials.add(Species::Purple.into()),
+            bomb: materials.add(Species::Bomb.into()),
+            rainbow: materials.add(ColorMaterial {
+                color: Color::WHITE,
+                texture: Some(images.add(Self::rainbow_image())),
+            }),
+            fireball: materials.add(Species::Fireball.into()),
+            stone: materials.add(Species::Stone.into()),
+            ice: materials.add(Color::rgba(0.8, 0.95, 1.0, 0.4).into()),
+            thick_ice: materials.add(Color::rgba(0.8, 0.95, 1.0, 0.7).into()),
+            collectible: materials.add(Color::GOLD.into()),
             side_wall: materials.add(Color::AZURE.with_a(0.2).into()),
             game_over_line: materials.add(Color::RED.with_a(0.1).into()),
             aim_target: materials.add(Color::INDIGO.with_a(0.5).into()),
             aim_line: materials.add(Color::INDIGO.with_a(0.5).into()),
+            pop_hint: materials.add(Color::WHITE.with_a(0.7).into()),
         }
     }
 
@@ -61,6 +94,46 @@ impl GameplayMaterials {
             Species::Yellow => self.yellow.clone(),
             Species::White => self.white.clone(),
             Species::Purple => self.purple.clone(),
+            Species::Bomb => self.bomb.clone(),
+            Species::Rainbow => self.rainbow.clone(),
+            Species::Fireball => self.fireball.clone(),
+            Species::Stone => self.stone.clone(),
         }
     }
+
+    pub fn ice_for_layers(&self, layers: u8) -> Handle<ColorMaterial> {
+        match layers {
+            1 => self.ice.clone(),
+            _ => self.thick_ice.clone(),
+        }
+    }
+
+    /// stripes of grid colours, blended by texture sampler across the ball
+    fn rainbow_image() -> Image {
+        let stripes = [
+            Species::Red,
+            Species::Yellow,
+            Species::Green,
+            Species::Blau,
+            Species::Blue,
+            Species::Purple,
+        ];
+        let data: Vec<u8> = stripes
+            .iter()
+            .flat_map(|species| {
+                let color: Color = (*species).into();
+                color.as_rgba_u8()
+            })
+            .collect();
+        Image::new(
+            Extent3d {
+                width: stripes.len() as u32,
+                height: 1,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            data,
+            TextureFormat::Rgba8UnormSrgb,
+        )
+    }
 }
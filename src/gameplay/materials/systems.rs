@@ -1,10 +1,14 @@
 use bevy::{
-    prelude::{Assets, Commands, ResMut},
+    prelude::{Assets, Commands, Image, ResMut},
     sprite::ColorMaterial,
 };
 
 use super::resources::GameplayMaterials;
 
-pub fn setup_resources(mut commands: Commands, mut materials: ResMut<Assets<ColorMaterial>>) {
-    commands.insert_resource(GameplayMaterials::new(&mut materials));
+pub fn setup_resources(
+    mut commands: Commands,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    mut images: ResMut<Assets<Image>>,
+) {
+    commands.insert_resource(GameplayMaterials::new(&mut materials, &mut images));
 }
@@ -5,33 +5,40 @@ use crate::{components::AppState, ui::systems::interact_with_next_state_button};
 use self::{
     ball::ProjectilePlugin,
     events::{
-        FindCluster, MoveDownTopWall, ProjectileReload, SnapProjectile, SpawnRow,
-        UpdateScoreCounter,
+        CrackIce, FindCluster, MoveDownTopWall, ProjectileBounced, ProjectilePopped,
+        ProjectileReload, ProjectileSettled, ScriptedShot, ShotFired, SnapProjectile, SpawnRow,
+        SwapProjectile, UpdateScoreCounter,
     },
     grid::GridPlugin,
+    level::LevelPlugin,
     lines::LinesPlugin,
     main_camera::MainCameraPlugin,
     materials::MaterialsPlugin,
     meshes::MeshesPlugin,
     panels::PanelsPlugin,
     physics::PhysicsPlugin,
+    playfield::PlayfieldPlugin,
+    rng::RngPlugin,
     systems::{check_game_over, check_game_win, cleanup_events, keydown_detect, setup_first_turn},
     walls::WallsPlugin,
 };
 
-mod ball;
+pub mod ball;
 pub mod constants;
-mod events;
-mod grid;
+pub mod events;
+pub mod grid;
+pub mod level;
 mod lines;
 mod main_camera;
 mod materials;
 mod meshes;
-mod panels;
+pub mod panels;
 mod physics;
+pub mod playfield;
+pub mod rng;
 mod systems;
 mod utils;
-mod walls;
+pub mod walls;
 
 pub struct GameplayPlugin;
 
@@ -47,6 +54,9 @@ impl Plugin for GameplayPlugin {
             GridPlugin,
             ProjectilePlugin,
             PanelsPlugin,
+            RngPlugin,
+            PlayfieldPlugin,
+            LevelPlugin,
         ))
         .add_event::<ProjectileReload>()
         .add_event::<SnapProjectile>()
@@ -54,6 +64,13 @@ impl Plugin for GameplayPlugin {
         .add_event::<MoveDownTopWall>()
         .add_event::<SpawnRow>()
         .add_event::<FindCluster>()
+        .add_event::<CrackIce>()
+        .add_event::<ScriptedShot>()
+        .add_event::<SwapProjectile>()
+        .add_event::<ShotFired>()
+        .add_event::<ProjectileBounced>()
+        .add_event::<ProjectileSettled>()
+        .add_event::<ProjectilePopped>()
         .add_systems(OnEnter(AppState::Gameplay), setup_first_turn)
         .add_systems(
             Update,
@@ -8,3 +8,6 @@ pub struct TurnText {}
 
 #[derive(Component)]
 pub struct LevelText {}
+
+#[derive(Component)]
+pub struct ObjectiveText {}
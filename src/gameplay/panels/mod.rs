@@ -3,13 +3,16 @@ use bevy::prelude::{in_state, App, IntoSystemConfigs, OnEnter, OnExit, Plugin, U
 use crate::{components::AppState, ui::systems::cleanup_full_row};
 
 use self::{
-    resources::{MoveDownCounter, ScoreCounter, SpawnRowsLeft, TurnCounter},
-    systems::{setup_resources, setup_ui, update_ui},
+    resources::{CollectibleCounter, MoveDownCounter, ScoreCounter, SpawnRowsLeft, TurnCounter},
+    systems::{
+        count_collectibles, count_turns, setup_resources, setup_ui, update_level_text,
+        update_objective_text, update_ui,
+    },
 };
 
 mod components;
 pub mod resources;
-mod systems;
+pub mod systems;
 
 pub struct PanelsPlugin;
 
@@ -19,8 +22,19 @@ impl Plugin for PanelsPlugin {
             .init_resource::<MoveDownCounter>()
             .init_resource::<ScoreCounter>()
             .init_resource::<SpawnRowsLeft>()
+            .init_resource::<CollectibleCounter>()
             .add_systems(OnEnter(AppState::GameplayInit), (setup_resources, setup_ui))
-            .add_systems(Update, update_ui.run_if(in_state(AppState::Gameplay)))
+            .add_systems(
+                Update,
+                (
+                    count_turns,
+                    update_ui,
+                    update_level_text,
+                    count_collectibles,
+                    update_objective_text,
+                )
+                    .run_if(in_state(AppState::Gameplay)),
+            )
             .add_systems(OnExit(AppState::Gameplay), cleanup_full_row);
     }
 }
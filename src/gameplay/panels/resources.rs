@@ -11,3 +11,10 @@ pub struct SpawnRowsLeft(pub u32);
 
 #[derive(Resource, Default)]
 pub struct ScoreCounter(pub u32);
+
+/// collectibles trapped in grid at start and still left there
+#[derive(Resource, Default)]
+pub struct CollectibleCounter {
+    pub total: u32,
+    pub left: u32,
+}
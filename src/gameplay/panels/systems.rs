@@ -1,20 +1,27 @@
 use bevy::{
-    prelude::{Commands, Query, Res, ResMut, With, Without},
+    prelude::{Commands, EventReader, Query, Res, ResMut, With, Without},
     text::Text,
-    window::{PrimaryWindow, Window},
 };
 use bevy_pkv::PkvStore;
 
 use crate::{
     components::AppState,
     constants::{
-        INIT_ROWS_KEY, MOVE_DOWN_AFTER_KEY, TOTAL_COLORS_KEY, TOTAL_COLUMNS_KEY, TOTAL_ROWS_KEY,
+        GRID_MODE_KEY, INIT_ROWS_KEY, MOVE_DOWN_AFTER_KEY, TOTAL_COLORS_KEY, TOTAL_COLUMNS_KEY,
+        TOTAL_ROWS_KEY,
+    },
+    gameplay::{
+        ball::components::Collectible,
+        events::{ProjectilePopped, ProjectileSettled},
+        grid::resources::{CooldownMoveCounter, Grid},
+        level::resources::{Objectives, SelectedLevel},
+        playfield::resources::Playfield,
     },
-    gameplay::grid::resources::{CooldownMoveCounter, Grid},
     loading::{font_assets::FontAssets, sprite_assets::SpriteAssets},
     settings_menu::utils::{
         colors_utils::read_total_colors,
         columns_utils::read_init_cols,
+        grid_mode_utils::read_grid_mode,
         move_down_utils::read_move_down,
         rows_utils::{read_init_rows, read_total_rows},
     },
@@ -29,8 +36,8 @@ use crate::{
 };
 
 use super::{
-    components::{LevelText, ScoreText, TurnText},
-    resources::{MoveDownCounter, ScoreCounter, SpawnRowsLeft, TurnCounter},
+    components::{LevelText, ObjectiveText, ScoreText, TurnText},
+    resources::{CollectibleCounter, MoveDownCounter, ScoreCounter, SpawnRowsLeft, TurnCounter},
 };
 
 pub fn setup_resources(
@@ -45,6 +52,7 @@ pub fn setup_resources(
     turn_counter.0 = 0;
     move_counter.0 = 0;
     score_counter.0 = 0;
+    commands.insert_resource(CollectibleCounter::default());
     let move_down_after = read_move_down(MOVE_DOWN_AFTER_KEY, &pkv);
     commands.insert_resource(CooldownMoveCounter::init(move_down_after));
 
@@ -53,6 +61,7 @@ pub fn setup_resources(
     grid.init_rows = read_init_rows(INIT_ROWS_KEY, &pkv);
     grid.total_rows = read_total_rows(TOTAL_ROWS_KEY, &pkv);
 
+    grid.mode = read_grid_mode(GRID_MODE_KEY, &pkv);
     grid.calc_last_active_row();
 
     let left_rows = grid.total_rows as i32 - grid.init_rows as i32;
@@ -67,10 +76,10 @@ pub fn setup_ui(
     font_assets: Res<FontAssets>,
     sprite_assets: Res<SpriteAssets>,
     text_colors: Res<UIMenuTextColors>,
-    window_query: Query<&Window, With<PrimaryWindow>>,
+    playfield: Res<Playfield>,
     button_colors: Res<UIMenuButtonColors>,
 ) {
-    let window_width = window_query.single().width();
+    let window_width = playfield.width;
     build_flex_full_row_evenly(&mut commands, |parent| {
         append_middle_icon_button(
             parent,
@@ -107,6 +116,14 @@ pub fn setup_ui(
             &text_colors,
             Some(LevelText {}),
         );
+        append_responsive_text(
+            parent,
+            window_width,
+            "",
+            &font_assets,
+            &text_colors,
+            Some(ObjectiveText {}),
+        );
     });
 }
 
@@ -120,10 +137,6 @@ pub fn update_ui(
     spawn_rows_left: Res<SpawnRowsLeft>,
     cooldown_move_counter: Res<CooldownMoveCounter>,
     mut turn_text_query: Query<&mut Text, (With<TurnText>, Without<ScoreText>, Without<LevelText>)>,
-    mut level_text_query: Query<
-        &mut Text,
-        (With<LevelText>, Without<ScoreText>, Without<TurnText>),
-    >,
 ) {
     for mut score_text in &mut score_text_query {
         score_text.sections[0].value = format!("Очки: {:?} ", score_counter.0);
@@ -134,7 +147,62 @@ pub fn update_ui(
             turn_counter.0, spawn_rows_left.0, cooldown_move_counter.value
         );
     }
+}
+
+pub fn update_level_text(
+    selected_level: Res<SelectedLevel>,
+    grid: Res<Grid>,
+    mut level_text_query: Query<&mut Text, With<LevelText>>,
+) {
+    // random grid becomes one row higher after each win
+    let level = match selected_level.0 {
+        Some(level_index) => level_index as u32 + 1,
+        None => grid.total_rows as u32,
+    };
     for mut level_text in &mut level_text_query {
-        level_text.sections[0].value = format!("Уровень: ");
+        level_text.sections[0].value = format!("Уровень: {}", level);
     }
 }
+
+/// collectible is freed when its ball is despawned by pop or drop,
+/// out ball spawns with the same commands, so win waits for its animation
+pub fn count_collectibles(
+    collectibles_query: Query<(), With<Collectible>>,
+    mut collectible_counter: ResMut<CollectibleCounter>,
+) {
+    let left = collectibles_query.iter().count() as u32;
+    if left == collectible_counter.left {
+        return;
+    }
+    if left < collectible_counter.left {
+        println!(
+            "Collectibles freed {} left {}",
+            collectible_counter.left - left,
+            left
+        );
+    }
+    collectible_counter.left = left;
+}
+
+pub fn update_objective_text(
+    objectives: Res<Objectives>,
+    mut objective_text_query: Query<&mut Text, With<ObjectiveText>>,
+) {
+    for mut objective_text in &mut objective_text_query {
+        objective_text.sections[0].value = format!(
+            "Цель: {}",
+            objectives.objective.describe(&objectives.progress)
+        );
+    }
+}
+
+/// turn ends when projectile snapped or was popped before snap
+pub fn count_turns(
+    mut projectile_settled_events: EventReader<ProjectileSettled>,
+    mut projectile_popped_events: EventReader<ProjectilePopped>,
+    mut turn_counter: ResMut<TurnCounter>,
+) {
+    turn_counter.0 += (projectile_settled_events.len() + projectile_popped_events.len()) as u32;
+    projectile_settled_events.clear();
+    projectile_popped_events.clear();
+}
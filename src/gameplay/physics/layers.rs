@@ -6,4 +6,6 @@ pub enum Layer {
     Grid,
     Out,
     Lines,
+    /// piercing projectile, senses grid balls and walls without collision response
+    Fireball,
 }
@@ -0,0 +1,15 @@
+use bevy::prelude::{App, Plugin, PreUpdate};
+
+use self::{resources::Playfield, systems::sync_playfield_with_window};
+
+pub mod resources;
+mod systems;
+
+pub struct PlayfieldPlugin;
+
+impl Plugin for PlayfieldPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Playfield>()
+            .add_systems(PreUpdate, sync_playfield_with_window);
+    }
+}
@@ -0,0 +1,27 @@
+use bevy::prelude::Resource;
+
+use crate::gameplay::constants::{PLAYFIELD_HEIGHT, PLAYFIELD_WIDTH};
+
+/// size of the visible game area, follows primary window or stays virtual when headless
+#[derive(Resource, Clone, Copy, Debug)]
+pub struct Playfield {
+    pub width: f32,
+    pub height: f32,
+}
+
+impl Default for Playfield {
+    fn default() -> Self {
+        Self::new(PLAYFIELD_WIDTH, PLAYFIELD_HEIGHT)
+    }
+}
+
+impl Playfield {
+    pub fn new(width: f32, height: f32) -> Self {
+        Self { width, height }
+    }
+
+    /// world y of the line placed `bottom` pixels above the bottom edge
+    pub fn bottom_y(&self, bottom: f32) -> f32 {
+        -(self.height - bottom - self.height / 2.0)
+    }
+}
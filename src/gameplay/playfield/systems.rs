@@ -0,0 +1,18 @@
+use bevy::{
+    prelude::{Query, ResMut, With},
+    window::{PrimaryWindow, Window},
+};
+
+use super::resources::Playfield;
+
+pub fn sync_playfield_with_window(
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    mut playfield: ResMut<Playfield>,
+) {
+    if let Ok(window) = window_query.get_single() {
+        if playfield.width != window.width() || playfield.height != window.height() {
+            playfield.width = window.width();
+            playfield.height = window.height();
+        }
+    }
+}
@@ -0,0 +1,17 @@
+use bevy::prelude::{App, OnEnter, Plugin};
+
+use crate::{components::AppState, constants::SEED_ARG, utils::read_arg};
+
+use self::{resources::GameRng, systems::setup_game_rng};
+
+pub mod resources;
+pub mod systems;
+
+pub struct RngPlugin;
+
+impl Plugin for RngPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(GameRng::new(read_arg::<u64>(SEED_ARG)))
+            .add_systems(OnEnter(AppState::GameplayInit), setup_game_rng);
+    }
+}
@@ -0,0 +1,33 @@
+use bevy::prelude::Resource;
+use fastrand::Rng;
+
+#[derive(Resource)]
+pub struct GameRng {
+    pub seed: u64,
+    pub arg_seed: Option<u64>,
+    pub rng: Rng,
+}
+
+impl GameRng {
+    pub fn new(arg_seed: Option<u64>) -> Self {
+        let seed = arg_seed.unwrap_or_else(|| fastrand::u64(..));
+        Self {
+            seed,
+            arg_seed,
+            rng: Rng::with_seed(seed),
+        }
+    }
+
+    /// command line seed wins over the one from settings, otherwise every game is random
+    pub fn pick_seed(&self, settings_seed: Option<u64>) -> u64 {
+        match self.arg_seed.or(settings_seed) {
+            Some(seed) => seed,
+            None => fastrand::u64(..),
+        }
+    }
+
+    pub fn reseed(&mut self, seed: u64) {
+        self.seed = seed;
+        self.rng = Rng::with_seed(seed);
+    }
+}
@@ -0,0 +1,17 @@
+use bevy::prelude::ResMut;
+use bevy_pkv::PkvStore;
+
+use crate::{
+    constants::{LAST_SEED_KEY, SEED_KEY},
+    settings_menu::utils::seed_utils::read_seed,
+};
+
+use super::resources::GameRng;
+
+pub fn setup_game_rng(mut pkv: ResMut<PkvStore>, mut game_rng: ResMut<GameRng>) {
+    let seed = game_rng.pick_seed(read_seed(SEED_KEY, &pkv));
+    game_rng.reseed(seed);
+    println!("game seed {}", seed);
+    pkv.set_string(LAST_SEED_KEY, &seed.to_string())
+        .expect("failed to save last seed");
+}
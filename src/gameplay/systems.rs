@@ -1,21 +1,22 @@
-use bevy::{
-    prelude::{
-        warn, Entity, EventReader, EventWriter, Input, KeyCode, NextState, Query, Res, ResMut,
-        With, Without,
-    },
-    window::{PrimaryWindow, Window},
+use bevy::prelude::{
+    warn, Entity, EventReader, EventWriter, Input, KeyCode, NextState, Query, Res, ResMut, With,
+    Without,
 };
 use bevy_pkv::PkvStore;
 use bevy_xpbd_2d::prelude::Position;
 
-use crate::components::AppState;
+use crate::{components::AppState, ui::resources::PointerCooldown};
 
 use super::{
     ball::components::{GridBall, OutBall, ProjectileBall},
-    constants::GAME_OVER_BOTTOM,
-    events::{FindCluster, MoveDownTopWall, ProjectileReload, SnapProjectile, SpawnRow},
+    constants::{GAME_OVER_BOTTOM, SWAP_PROJECTILE_KEYCODE},
+    events::{
+        FindCluster, MoveDownTopWall, ProjectileReload, SnapProjectile, SpawnRow, SwapProjectile,
+    },
     grid::resources::Grid,
+    level::resources::{Objectives, SelectedLevel},
     lines::components::LineType,
+    playfield::resources::Playfield,
     utils::increment_init_rows,
 };
 
@@ -25,15 +26,14 @@ pub fn setup_first_turn(mut begin_turn: EventWriter<ProjectileReload>) {
 
 pub fn check_game_over(
     mut app_state_next_state: ResMut<NextState<AppState>>,
-    window_query: Query<&Window, With<PrimaryWindow>>,
+    playfield: Res<Playfield>,
     mut lines_query: Query<&mut Position, With<LineType>>,
     balls_query: Query<
         (Entity, &Position),
         (With<GridBall>, Without<ProjectileBall>, Without<LineType>),
     >,
 ) {
-    let window = window_query.single();
-    let game_over_bottom = -(window.height() - GAME_OVER_BOTTOM - window.height() / 2.0);
+    let game_over_bottom = playfield.bottom_y(GAME_OVER_BOTTOM);
 
     for mut line_position in lines_query.iter_mut() {
         line_position.y = game_over_bottom
@@ -55,11 +55,19 @@ pub fn check_game_win(
     mut app_state_next_state: ResMut<NextState<AppState>>,
     mut grid: ResMut<Grid>,
     mut pkv: ResMut<PkvStore>,
-    balls_query: Query<&GridBall, (With<GridBall>, Without<ProjectileBall>)>,
     out_balls_query: Query<&OutBall, With<OutBall>>,
+    selected_level: Res<SelectedLevel>,
+    objectives: Res<Objectives>,
 ) {
-    if balls_query.iter().len() == 0 && out_balls_query.iter().count() == 0 {
-        increment_init_rows(grid.as_mut(), &mut pkv);
+    if objectives.objective.is_met(&objectives.progress) && out_balls_query.iter().count() == 0 {
+        println!(
+            "Objective met {}",
+            objectives.objective.describe(&objectives.progress)
+        );
+        // campaign progress is saved on win screen
+        if selected_level.0.is_none() {
+            increment_init_rows(grid.as_mut(), &mut pkv);
+        }
         app_state_next_state.set(AppState::GameWin);
     }
 }
@@ -69,11 +77,21 @@ pub fn keydown_detect(
     keyboard_input_key_code: Res<Input<KeyCode>>,
     mut grid: ResMut<Grid>,
     mut pkv: ResMut<PkvStore>,
+    selected_level: Res<SelectedLevel>,
+    mut pointer_cooldown: ResMut<PointerCooldown>,
+    mut writer_swap_projectile: EventWriter<SwapProjectile>,
 ) {
     if keyboard_input_key_code.any_just_released([KeyCode::Escape]) {
         app_state_next_state.set(AppState::GameOver);
     }
-    if keyboard_input_key_code.any_just_released([KeyCode::Space]) {
+    if keyboard_input_key_code.any_just_released([SWAP_PROJECTILE_KEYCODE])
+        && !pointer_cooldown.started
+    {
+        pointer_cooldown.started = true;
+        writer_swap_projectile.send(SwapProjectile);
+    }
+    // campaign levels are won by objective only, so Space does not give away stars
+    if keyboard_input_key_code.any_just_released([KeyCode::Space]) && selected_level.0.is_none() {
         increment_init_rows(grid.as_mut(), &mut pkv);
         app_state_next_state.set(AppState::GameWin);
     }
@@ -85,6 +103,7 @@ pub fn cleanup_events(
     mut move_down_events: EventReader<MoveDownTopWall>,
     mut spawn_row_events: EventReader<SpawnRow>,
     mut find_cluster_events: EventReader<FindCluster>,
+    mut swap_projectile_events: EventReader<SwapProjectile>,
 ) {
     if projectile_reload_events.len() > 0 {
         projectile_reload_events.clear();
@@ -101,4 +120,7 @@ pub fn cleanup_events(
     if find_cluster_events.len() > 0 {
         find_cluster_events.clear();
     }
+    if !swap_projectile_events.is_empty() {
+        swap_projectile_events.clear();
+    }
 }
@@ -6,7 +6,7 @@ use self::systems::{cleanup_level_walls, setup_level_walls};
 
 pub mod components;
 mod systems;
-mod wall_bundle;
+pub mod wall_bundle;
 
 pub struct WallsPlugin;
 
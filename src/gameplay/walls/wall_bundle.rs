@@ -19,8 +19,6 @@ pub struct WallBundle;
 impl WallBundle {
     fn new(
         pos: Vec2,
-        width: f32,
-        height: f32,
         wall_type: WallType,
         meshes: &mut ResMut<Assets<Mesh>>,
         gameplay_materials: &Res<GameplayMaterials>,
@@ -28,16 +26,56 @@ impl WallBundle {
         (
             MaterialMesh2dBundle {
                 mesh: meshes
-                    .add(shape::Quad::new(Vec2::new(width, height)).into())
+                    .add(shape::Quad::new(Self::size(&wall_type)).into())
                     .into(),
                 material: gameplay_materials.side_wall.clone(),
                 transform: Transform::from_translation(pos.extend(WALL_Z_INDEX)),
                 ..Default::default()
             },
+            Self::physics(pos, &wall_type),
             wall_type,
-            Collider::cuboid(width, height),
+        )
+    }
+
+    fn size(wall_type: &WallType) -> Vec2 {
+        match wall_type.is_side() {
+            true => Vec2::new(WALL_SIDE_WIDTH, WALL_SIDE_HEIGHT),
+            false => Vec2::new(WALL_TOP_WIDTH, WALL_TOP_HEIGHT),
+        }
+    }
+
+    /// collision components, shared with the aim simulation
+    pub fn physics(pos: Vec2, wall_type: &WallType) -> impl Bundle {
+        let size = Self::size(wall_type);
+        let (rigid_body, restitution, friction) = match wall_type.is_side() {
+            true => (
+                RigidBody::Static,
+                Restitution {
+                    coefficient: 1.0,
+                    combine_rule: CoefficientCombine::Max,
+                },
+                Friction {
+                    dynamic_coefficient: 0.0,
+                    static_coefficient: 0.0,
+                    combine_rule: CoefficientCombine::Min,
+                },
+            ),
+            false => (
+                RigidBody::Kinematic,
+                Restitution {
+                    coefficient: 0.0,
+                    combine_rule: CoefficientCombine::Multiply,
+                },
+                Friction::default(),
+            ),
+        };
+        (
+            Collider::cuboid(size.x, size.y),
             Position(pos),
-            CollisionLayers::new([Layer::Walls], [Layer::Grid]),
+            CollisionLayers::new([Layer::Walls], [Layer::Grid, Layer::Fireball]),
+            rigid_body,
+            restitution,
+            friction,
         )
     }
 
@@ -48,44 +86,11 @@ impl WallBundle {
         meshes: &mut ResMut<Assets<Mesh>>,
         gameplay_materials: &Res<GameplayMaterials>,
     ) {
-        let is_side_wall = match wall_type {
-            WallType::Left | WallType::Right => true,
-            WallType::Top => false,
-        };
-        let mut entity_commands = commands.spawn(Self::new(
-            pos,
-            match is_side_wall {
-                true => WALL_SIDE_WIDTH,
-                false => WALL_TOP_WIDTH,
-            },
-            match is_side_wall {
-                true => WALL_SIDE_HEIGHT,
-                false => WALL_TOP_HEIGHT,
-            },
-            wall_type,
-            meshes,
-            gameplay_materials,
-        ));
-        if is_side_wall {
-            entity_commands
-                .insert(RigidBody::Static)
-                .insert(Restitution {
-                    coefficient: 1.0,
-                    combine_rule: CoefficientCombine::Max,
-                })
-                .insert(Friction {
-                    dynamic_coefficient: 0.0,
-                    static_coefficient: 0.0,
-                    combine_rule: CoefficientCombine::Min,
-                });
-        } else {
-            entity_commands
-                .insert(TopWall)
-                .insert(RigidBody::Kinematic)
-                .insert(Restitution {
-                    coefficient: 0.0,
-                    combine_rule: CoefficientCombine::Multiply,
-                });
+        let is_top = wall_type.is_top();
+        let mut entity_commands =
+            commands.spawn(Self::new(pos, wall_type, meshes, gameplay_materials));
+        if is_top {
+            entity_commands.insert(TopWall);
         }
     }
 }
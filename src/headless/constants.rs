@@ -0,0 +1,4 @@
+pub const HEADLESS_FRAME_TIME: f32 = 1.0 / 60.0; // seconds
+pub const HEADLESS_MAX_FRAMES: u32 = 60 * 60 * 10; // ten minutes of game time
+pub const HEADLESS_DEFAULT_SHOTS: u32 = 200;
+pub const HEADLESS_PKV_DIR: &str = "bevy-wasm-ball-shooter-headless";
@@ -0,0 +1,38 @@
+use bevy::prelude::{in_state, App, IntoSystemConfigs, OnEnter, Plugin, Update};
+
+use crate::{
+    components::AppState, gameplay::grid::systems::projectile_systems::end_projectile_shot,
+};
+
+use self::{
+    resources::{HeadlessOutcome, ShotScript},
+    systems::{feed_scripted_shots, log_projectile_lifecycle, record_outcome},
+};
+
+pub mod constants;
+pub mod resources;
+mod systems;
+#[cfg(test)]
+pub mod tests;
+pub mod utils;
+
+/// drives gameplay from shot script instead of pointer and remembers how the game ended
+pub struct HeadlessPlugin;
+
+impl Plugin for HeadlessPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ShotScript>()
+            .init_resource::<HeadlessOutcome>()
+            .add_systems(
+                Update,
+                (
+                    feed_scripted_shots,
+                    // shot which ends the game is logged before the state leaves gameplay
+                    log_projectile_lifecycle.after(end_projectile_shot),
+                )
+                    .run_if(in_state(AppState::Gameplay)),
+            )
+            .add_systems(OnEnter(AppState::GameOver), record_outcome)
+            .add_systems(OnEnter(AppState::GameWin), record_outcome);
+    }
+}
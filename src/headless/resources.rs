@@ -0,0 +1,31 @@
+use std::collections::VecDeque;
+
+use bevy::prelude::{Entity, Resource, Vec2};
+
+use crate::{components::AppState, gameplay::events::SnapReason};
+
+#[derive(Resource, Default)]
+pub struct ShotScript {
+    pub targets: VecDeque<Vec2>,
+    pub last_projectile: Option<Entity>,
+}
+
+impl ShotScript {
+    pub fn new(targets: Vec<Vec2>) -> Self {
+        Self {
+            targets: targets.into(),
+            last_projectile: None,
+        }
+    }
+}
+
+#[derive(Resource, Default, Clone, Debug)]
+pub struct HeadlessOutcome {
+    pub state: Option<AppState>,
+    pub frames: u32,
+    pub shots: u32,
+    pub score: u32,
+    pub turns: u32,
+    pub bounces: u32,
+    pub settle_reasons: Vec<SnapReason>,
+}
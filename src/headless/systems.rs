@@ -0,0 +1,67 @@
+use bevy::prelude::{Entity, EventReader, EventWriter, Query, Res, ResMut, State};
+
+use crate::{
+    components::AppState,
+    gameplay::{
+        ball::components::ProjectileBall,
+        events::{ProjectileBounced, ProjectilePopped, ProjectileSettled, ScriptedShot, ShotFired},
+    },
+};
+
+use super::resources::{HeadlessOutcome, ShotScript};
+
+pub fn feed_scripted_shots(
+    mut shot_script: ResMut<ShotScript>,
+    mut outcome: ResMut<HeadlessOutcome>,
+    projectile_query: Query<(Entity, &ProjectileBall)>,
+    mut scripted_shot_events: EventWriter<ScriptedShot>,
+) {
+    for (projectile_entity, projectile_ball) in projectile_query.iter() {
+        if !projectile_ball.state.is_loaded()
+            || shot_script.last_projectile == Some(projectile_entity)
+        {
+            continue;
+        }
+        if let Some(target) = shot_script.targets.pop_front() {
+            shot_script.last_projectile = Some(projectile_entity);
+            outcome.shots += 1;
+            scripted_shot_events.send(ScriptedShot { target });
+        }
+    }
+}
+
+pub fn record_outcome(app_state: Res<State<AppState>>, mut outcome: ResMut<HeadlessOutcome>) {
+    outcome.state = Some(*app_state.get());
+}
+
+/// logs projectile lifecycle events, so each shot of the script can be explained
+pub fn log_projectile_lifecycle(
+    mut shot_fired_events: EventReader<ShotFired>,
+    mut projectile_bounced_events: EventReader<ProjectileBounced>,
+    mut projectile_settled_events: EventReader<ProjectileSettled>,
+    mut projectile_popped_events: EventReader<ProjectilePopped>,
+    mut outcome: ResMut<HeadlessOutcome>,
+) {
+    for ShotFired {
+        projectile_entity,
+        velocity,
+    } in shot_fired_events.iter()
+    {
+        println!("Shot fired {:?} velocity {}", projectile_entity, velocity);
+    }
+    for ProjectileBounced { projectile_entity } in projectile_bounced_events.iter() {
+        println!("Projectile bounced {:?}", projectile_entity);
+        outcome.bounces += 1;
+    }
+    for ProjectileSettled {
+        projectile_entity,
+        reason,
+    } in projectile_settled_events.iter()
+    {
+        println!("Projectile settled {:?} {:?}", projectile_entity, reason);
+        outcome.settle_reasons.push(*reason);
+    }
+    for ProjectilePopped { projectile_entity } in projectile_popped_events.iter() {
+        println!("Projectile popped {:?}", projectile_entity);
+    }
+}
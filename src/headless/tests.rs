@@ -0,0 +1,897 @@
+use std::path::Path;
+
+use bevy::{
+    prelude::{App, Assets, Entity, Vec2, With, Without},
+    utils::{HashMap, HashSet},
+};
+use bevy_pkv::PkvStore;
+use bevy_xpbd_2d::prelude::Position;
+use fastrand::Rng;
+use hexx::Hex;
+
+use crate::{
+    components::AppState,
+    constants::{GRID_MODE_KEY, LEVEL_PATHS},
+    gameplay::{
+        ball::components::{Chameleon, Fireball, Frozen, GridBall, ProjectileBall, Species},
+        constants::{
+            BALL_DIAMETER, BALL_RADIUS, BOMB_BLAST_RINGS, NEIGHBOUR_POSITION_TOLERANCE,
+            WALL_TOP_HEIGHT,
+        },
+        events::SnapReason,
+        grid::{
+            components::SettleDetector,
+            resources::{Grid, GridMode, SettleThresholds, SpatialIndex},
+            utils::{find_blast, find_cluster},
+        },
+        level::resources::{ObjectiveProgress, SelectedLevel},
+        panels::resources::TurnCounter,
+        playfield::resources::Playfield,
+        walls::components::TopWall,
+    },
+    loading::level_assets::{LevelAsset, LevelAssets, LevelError, LevelObjective},
+};
+
+use super::{
+    resources::{HeadlessOutcome, ShotScript},
+    utils::{build_headless_app, simulate_headless_game},
+};
+
+const TEST_SEED: u64 = 42;
+const TEST_MAX_FRAMES: u32 = 60 * 60; // one minute of game time
+const APPEAR_FRAMES: u32 = 60;
+const SETTLE_FRAMES: u32 = 60 * 10;
+
+/// one colour only, so the first shot pops the whole grid
+const SINGLE_COLOR_LEVEL: &str = r#"(
+    name: "Single color",
+    cols: 5,
+    rows: 2,
+    total_rows: 2,
+    colors: [Red],
+    move_down_after: 10,
+    stars: Turns(1, 1),
+    cells: [],
+)"#;
+
+/// colours hardly match and grid moves down after every shot
+const SINKING_LEVEL: &str = r#"(
+    name: "Sinking",
+    cols: 7,
+    rows: 7,
+    total_rows: 20,
+    colors: [Red, Blue, Green, Yellow, White, Purple, Blau],
+    move_down_after: 1,
+    stars: Turns(1, 1),
+    cells: [],
+)"#;
+
+/// no neighbours share colour, so pushed grid balls do not pop before projectile settles,
+/// bottom row balls are free to move and top row ones hold the grid
+const ALTERNATING_LEVEL: &str = r#"(
+    name: "Alternating",
+    cols: 5,
+    rows: 2,
+    total_rows: 2,
+    colors: [Red, Blue, Green, Yellow],
+    move_down_after: 10,
+    stars: Turns(1, 1),
+    cells: [
+        (x: -2, y: 0, species: Some(Red)),
+        (x: -1, y: 0, species: Some(Blue)),
+        (x: 0, y: 0, species: Some(Red)),
+        (x: 1, y: 0, species: Some(Blue)),
+        (x: 2, y: 0, species: Some(Red)),
+        (x: -2, y: -1, species: Some(Green)),
+        (x: -1, y: -1, species: Some(Yellow)),
+        (x: 0, y: -1, species: Some(Green)),
+        (x: 1, y: -1, species: Some(Yellow)),
+    ],
+)"#;
+
+/// frozen ball hangs next to the cluster which the first shot pops
+const THAW_LEVEL: &str = r#"(
+    name: "Thaw",
+    cols: 5,
+    rows: 1,
+    total_rows: 1,
+    colors: [Red],
+    move_down_after: 10,
+    stars: Turns(1, 1),
+    cells: [
+        (x: -2, y: 0, species: Some(Red), ice: 1),
+        (x: -1, y: 0, species: Some(Red)),
+        (x: 0, y: 0, species: Some(Red)),
+        (x: 1, y: 0, species: Some(Red)),
+        (x: 2, y: 0, species: Some(Red)),
+    ],
+)"#;
+
+/// collectible is popped with the first shot, ball behind the stone stays on board
+const COLLECTIBLE_LEVEL: &str = r#"(
+    name: "Collectible",
+    cols: 5,
+    rows: 1,
+    total_rows: 1,
+    colors: [Red],
+    move_down_after: 10,
+    stars: Turns(1, 1),
+    objective: Some(FreeCollectibles),
+    cells: [
+        (x: -2, y: 0, species: Some(Red)),
+        (x: -1, y: 0, species: Some(Stone)),
+        (x: 0, y: 0, species: Some(Red), collectible: true),
+        (x: 1, y: 0, species: Some(Red)),
+        (x: 2, y: 0, species: Some(Red)),
+    ],
+)"#;
+
+/// chameleon sits in the corner, away from where the shot lands, its new colour forms no cluster
+const CHAMELEON_LEVEL: &str = r#"(
+    name: "Chameleon",
+    cols: 7,
+    rows: 1,
+    total_rows: 1,
+    colors: [Red, Blue, Green],
+    move_down_after: 10,
+    stars: Turns(1, 1),
+    cells: [
+        (x: -3, y: 0, species: Some(Red), chameleon: true),
+        (x: -2, y: 0, species: Some(Green)),
+        (x: -1, y: 0, species: Some(Red)),
+        (x: 0, y: 0, species: Some(Blue)),
+        (x: 1, y: 0, species: Some(Red)),
+        (x: 2, y: 0, species: Some(Green)),
+        (x: 3, y: 0, species: Some(Red)),
+    ],
+)"#;
+
+/// stone sits in the bottom row of the middle column, fireball burns its way around it
+const FIREBALL_LEVEL: &str = r#"(
+    name: "Fireball",
+    cols: 5,
+    rows: 3,
+    total_rows: 3,
+    colors: [Red, Blue, Green, Yellow, White, Purple],
+    move_down_after: 10,
+    stars: Turns(1, 1),
+    cells: [
+        (x: 0, y: -2, species: Some(Stone)),
+    ],
+)"#;
+
+/// rainbow projectile has to count as red to pop six red balls with one shot
+const RAINBOW_POP_LEVEL: &str = r#"(
+    name: "Rainbow pop",
+    cols: 5,
+    rows: 1,
+    total_rows: 1,
+    colors: [Red],
+    move_down_after: 10,
+    stars: Turns(1, 1),
+    objective: Some(Pop(Red, 6)),
+    cells: [],
+)"#;
+
+/// headless app which plays the only level from RON text
+pub fn build_level_app(level_ron: &str, grid_mode: &str, targets: Vec<Vec2>) -> App {
+    let mut app = build_headless_app(Playfield::default(), TEST_SEED, targets);
+    app.world
+        .resource_mut::<PkvStore>()
+        .set_string(GRID_MODE_KEY, grid_mode)
+        .expect("failed to save grid mode");
+    let level = LevelAsset::from_bytes(level_ron.as_bytes()).expect("failed to parse level");
+    let handle = app.world.resource_mut::<Assets<LevelAsset>>().add(level);
+    app.insert_resource(LevelAssets {
+        levels: vec![handle],
+    })
+    .insert_resource(SelectedLevel(Some(0)));
+    app
+}
+
+pub fn play(mut app: App) -> HeadlessOutcome {
+    simulate_headless_game(&mut app, TEST_MAX_FRAMES)
+}
+
+/// straight up into the middle of grid
+fn center_shots(shots: usize) -> Vec<Vec2> {
+    vec![Vec2::new(0.0, Playfield::default().height / 2.0); shots]
+}
+
+#[test]
+fn single_shot_clears_single_color_level() {
+    for grid_mode in ["magnetic", "classic"] {
+        let outcome = play(build_level_app(
+            SINGLE_COLOR_LEVEL,
+            grid_mode,
+            center_shots(1),
+        ));
+        assert_eq!(outcome.state, Some(AppState::GameWin), "{}", grid_mode);
+        assert_eq!(outcome.shots, 1, "{}", grid_mode);
+        assert_eq!(outcome.turns, 1, "{}", grid_mode);
+        // 9 grid balls and projectile are popped as one cluster
+        assert_eq!(outcome.score, 10, "{}", grid_mode);
+    }
+}
+
+#[test]
+fn sinking_grid_ends_with_game_over() {
+    let corner = Vec2::new(-Playfield::default().width / 2.0, 0.0);
+    let outcome = play(build_level_app(SINKING_LEVEL, "magnetic", vec![corner; 30]));
+    assert_eq!(outcome.state, Some(AppState::GameOver));
+    assert!(outcome.shots < 30);
+}
+
+/// settle detector runs in magnetic mode only, thresholds leave one way to settle
+fn settle_reason(thresholds: SettleThresholds, is_touched_on_shot: bool) -> Option<SnapReason> {
+    let mut app = build_level_app(ALTERNATING_LEVEL, "magnetic", vec![]);
+    app.insert_resource(thresholds);
+    // grid balls appear with scale animation, projectile touches them only afterwards
+    simulate_headless_game(&mut app, APPEAR_FRAMES);
+    app.world
+        .resource_mut::<ShotScript>()
+        .targets
+        .extend(center_shots(1));
+    for _ in 0..SETTLE_FRAMES {
+        app.update();
+        if is_touched_on_shot {
+            let mut projectile_query = app.world.query::<(&ProjectileBall, &mut SettleDetector)>();
+            for (projectile_ball, mut settle_detector) in projectile_query.iter_mut(&mut app.world)
+            {
+                if projectile_ball.state.is_flying() {
+                    settle_detector.touch();
+                }
+            }
+        }
+    }
+    app.world
+        .resource::<HeadlessOutcome>()
+        .settle_reasons
+        .first()
+        .copied()
+}
+
+/// thresholds which never settle projectile
+fn never_settle() -> SettleThresholds {
+    SettleThresholds {
+        slow_velocity: -1.0,
+        reverse_velocity: f32::MIN,
+        low_energy: -1.0,
+        low_energy_time: f32::MAX,
+        timeout: f32::MAX,
+    }
+}
+
+#[test]
+fn projectile_settles_slow_on_touch() {
+    assert_eq!(
+        settle_reason(SettleThresholds::default(), false),
+        Some(SnapReason::Slow)
+    );
+}
+
+#[test]
+fn projectile_settles_slow_on_low_energy() {
+    let thresholds = SettleThresholds {
+        low_energy: f32::MAX,
+        low_energy_time: 0.0,
+        ..never_settle()
+    };
+    assert_eq!(settle_reason(thresholds, false), Some(SnapReason::Slow));
+}
+
+#[test]
+fn projectile_settles_reversed_after_touch() {
+    let thresholds = SettleThresholds {
+        reverse_velocity: f32::MAX,
+        ..never_settle()
+    };
+    assert_eq!(settle_reason(thresholds, false), Some(SnapReason::Reversed));
+}
+
+#[test]
+fn projectile_settles_on_timeout() {
+    let thresholds = SettleThresholds {
+        timeout: 0.0,
+        ..never_settle()
+    };
+    // contact response turns projectile away before its touch is seen, so touch is faked
+    assert_eq!(settle_reason(thresholds, true), Some(SnapReason::Timeout));
+}
+
+#[test]
+fn projectile_settles_sleeping() {
+    assert_eq!(
+        settle_reason(never_settle(), false),
+        Some(SnapReason::Sleeping)
+    );
+}
+
+#[test]
+fn neighbour_pop_thaws_frozen_ball() {
+    for grid_mode in ["magnetic", "classic"] {
+        let mut app = build_level_app(THAW_LEVEL, grid_mode, center_shots(1));
+        let outcome = simulate_headless_game(&mut app, SETTLE_FRAMES);
+        // 4 grid balls and projectile are popped, thawed ball is left alone
+        assert_eq!(outcome.score, 5, "{}", grid_mode);
+        let mut frozen_query = app.world.query_filtered::<(), With<Frozen>>();
+        assert_eq!(frozen_query.iter(&app.world).count(), 0, "{}", grid_mode);
+        let mut balls_query = app
+            .world
+            .query_filtered::<&GridBall, Without<ProjectileBall>>();
+        assert_eq!(
+            balls_query
+                .iter(&app.world)
+                .filter(|grid_ball| !grid_ball.is_ready_to_despawn)
+                .count(),
+            1,
+            "{}",
+            grid_mode
+        );
+    }
+}
+
+#[test]
+fn freed_collectible_wins_level() {
+    for grid_mode in ["magnetic", "classic"] {
+        let outcome = play(build_level_app(
+            COLLECTIBLE_LEVEL,
+            grid_mode,
+            center_shots(1),
+        ));
+        assert_eq!(outcome.state, Some(AppState::GameWin), "{}", grid_mode);
+        assert_eq!(outcome.shots, 1, "{}", grid_mode);
+        // 3 grid balls and projectile, stone and ball behind it stay
+        assert_eq!(outcome.score, 4, "{}", grid_mode);
+    }
+}
+
+#[test]
+fn chameleon_turns_after_shot() {
+    for grid_mode in ["magnetic", "classic"] {
+        let mut app = build_level_app(CHAMELEON_LEVEL, grid_mode, vec![]);
+        simulate_headless_game(&mut app, APPEAR_FRAMES);
+        let mut chameleon_query = app
+            .world
+            .query_filtered::<(Entity, &Species), With<Chameleon>>();
+        let (chameleon, species) = chameleon_query.single(&app.world);
+        assert_eq!(*species, Species::Red, "{}", grid_mode);
+        app.world
+            .resource_mut::<ShotScript>()
+            .targets
+            .extend(center_shots(1));
+        for _ in 0..SETTLE_FRAMES {
+            app.update();
+            if app.world.resource::<TurnCounter>().0 > 0 {
+                break;
+            }
+        }
+        assert_eq!(app.world.resource::<TurnCounter>().0, 1, "{}", grid_mode);
+        // grid resources see the new colour in the frame the turn ends
+        let (_, species) = chameleon_query.single(&app.world);
+        assert_eq!(*species, Species::Blue, "{}", grid_mode);
+        assert_eq!(
+            app.world
+                .resource::<Grid>()
+                .entities_to_species
+                .get(&chameleon),
+            Some(&Species::Blue),
+            "{}",
+            grid_mode
+        );
+    }
+}
+
+#[test]
+fn fireball_burns_column_to_top_wall() {
+    for grid_mode in ["magnetic", "classic"] {
+        let mut app = build_level_app(FIREBALL_LEVEL, grid_mode, vec![]);
+        simulate_headless_game(&mut app, APPEAR_FRAMES);
+        let mut projectile_query = app
+            .world
+            .query_filtered::<&mut Species, With<ProjectileBall>>();
+        *projectile_query.single_mut(&mut app.world) = Species::Fireball;
+        // projectile waits at x 0 and flies straight up,
+        // middle balls and the odd row ones next to them are on its path
+        let mut balls_query = app.world.query_filtered::<
+            (Entity, &Position, &Species),
+            (With<GridBall>, Without<ProjectileBall>),
+        >();
+        let on_path: Vec<(Entity, Species)> = balls_query
+            .iter(&app.world)
+            .filter(|(_, position, _)| position.x.abs() < BALL_RADIUS * 1.5)
+            .map(|(entity, _, species)| (entity, *species))
+            .collect();
+        assert!(on_path.len() >= 3, "{}", grid_mode);
+        app.world
+            .resource_mut::<ShotScript>()
+            .targets
+            .extend(center_shots(1));
+        let mut fireball_query = app.world.query_filtered::<&Position, With<Fireball>>();
+        let mut last_fireball_y = None;
+        for _ in 0..SETTLE_FRAMES {
+            app.update();
+            if let Ok(position) = fireball_query.get_single(&app.world) {
+                last_fireball_y = Some(position.y);
+            }
+            if app.world.resource::<TurnCounter>().0 > 0 {
+                break;
+            }
+        }
+        assert_eq!(app.world.resource::<TurnCounter>().0, 1, "{}", grid_mode);
+        let mut grid_ball_query = app.world.query::<&GridBall>();
+        for (entity, species) in on_path {
+            let is_left = grid_ball_query
+                .get(&app.world, entity)
+                .is_ok_and(|grid_ball| !grid_ball.is_ready_to_despawn);
+            assert_eq!(
+                is_left,
+                species == Species::Stone,
+                "{} {:?}",
+                grid_mode,
+                species
+            );
+        }
+        // shot ends when fireball touches the top wall, not somewhere in the grid
+        let mut top_wall_query = app.world.query_filtered::<&Position, With<TopWall>>();
+        let top_wall_y = top_wall_query.single(&app.world).y;
+        let last_fireball_y = last_fireball_y.expect("fireball was not shot");
+        assert!(
+            last_fireball_y + BALL_DIAMETER + WALL_TOP_HEIGHT / 2.0 > top_wall_y,
+            "{} {} {}",
+            grid_mode,
+            last_fireball_y,
+            top_wall_y
+        );
+    }
+}
+
+#[test]
+fn rainbow_pop_counts_as_cluster_colour() {
+    for grid_mode in ["magnetic", "classic"] {
+        let mut app = build_level_app(RAINBOW_POP_LEVEL, grid_mode, vec![]);
+        simulate_headless_game(&mut app, APPEAR_FRAMES);
+        let mut projectile_query = app
+            .world
+            .query_filtered::<&mut Species, With<ProjectileBall>>();
+        *projectile_query.single_mut(&mut app.world) = Species::Rainbow;
+        app.world
+            .resource_mut::<ShotScript>()
+            .targets
+            .extend(center_shots(1));
+        let outcome = play(app);
+        assert_eq!(outcome.state, Some(AppState::GameWin), "{}", grid_mode);
+        assert_eq!(outcome.shots, 1, "{}", grid_mode);
+    }
+}
+
+/// score and yellow pops are partly done, board is cleared and one of two collectibles is freed
+fn objective_progress() -> ObjectiveProgress {
+    ObjectiveProgress {
+        score: 50,
+        popped: HashMap::from_iter([(Species::Yellow, 2)]),
+        is_board_cleared: true,
+        is_top_row_cleared: false,
+        collectibles_total: 2,
+        collectibles_left: 1,
+    }
+}
+
+#[test]
+fn objective_is_met_by_progress() {
+    let progress = objective_progress();
+    assert!(LevelObjective::ClearBoard.is_met(&progress));
+    assert!(!LevelObjective::ClearTopRow.is_met(&progress));
+    assert!(LevelObjective::Score(50).is_met(&progress));
+    assert!(!LevelObjective::Score(51).is_met(&progress));
+    assert!(LevelObjective::Pop(Species::Yellow, 2).is_met(&progress));
+    assert!(!LevelObjective::Pop(Species::Red, 1).is_met(&progress));
+    assert!(!LevelObjective::FreeCollectibles.is_met(&progress));
+    // level without collectibles is not won by freeing them
+    assert!(!LevelObjective::FreeCollectibles.is_met(&ObjectiveProgress::default()));
+}
+
+#[test]
+fn objective_groups_combine_nested_objectives() {
+    let progress = objective_progress();
+    let met = LevelObjective::Score(50);
+    let not_met = LevelObjective::FreeCollectibles;
+    assert!(LevelObjective::All(vec![met.clone(), LevelObjective::ClearBoard]).is_met(&progress));
+    assert!(!LevelObjective::All(vec![met.clone(), not_met.clone()]).is_met(&progress));
+    assert!(LevelObjective::Any(vec![not_met.clone(), met.clone()]).is_met(&progress));
+    assert!(
+        !LevelObjective::Any(vec![not_met.clone(), LevelObjective::ClearTopRow]).is_met(&progress)
+    );
+    assert!(LevelObjective::Any(vec![
+        not_met.clone(),
+        LevelObjective::All(vec![met.clone(), LevelObjective::ClearBoard]),
+    ])
+    .is_met(&progress));
+    assert!(!LevelObjective::All(vec![
+        met,
+        LevelObjective::Any(vec![not_met, LevelObjective::ClearTopRow]),
+    ])
+    .is_met(&progress));
+}
+
+#[test]
+fn objective_description_shows_progress() {
+    let progress = objective_progress();
+    assert_eq!(
+        LevelObjective::ClearBoard.describe(&progress),
+        "очистить поле"
+    );
+    assert_eq!(
+        LevelObjective::Score(100).describe(&progress),
+        "очки 50/100"
+    );
+    // progress over the target is capped
+    assert_eq!(LevelObjective::Score(10).describe(&progress), "очки 10/10");
+    assert_eq!(
+        LevelObjective::Pop(Species::Yellow, 3).describe(&progress),
+        "жёлтые 2/3"
+    );
+    assert_eq!(
+        LevelObjective::FreeCollectibles.describe(&progress),
+        "сокровища 1/2"
+    );
+    assert_eq!(
+        LevelObjective::Any(vec![
+            LevelObjective::ClearBoard,
+            LevelObjective::All(vec![
+                LevelObjective::FreeCollectibles,
+                LevelObjective::Score(100)
+            ]),
+        ])
+        .describe(&progress),
+        "очистить поле или (сокровища 1/2 и очки 50/100)"
+    );
+}
+
+#[test]
+fn spatial_index_moves_ball_between_cells() {
+    let mut spatial_index = SpatialIndex::default();
+    let ball = Entity::from_raw(1);
+    let position = Vec2::new(0.9, 0.5) * NEIGHBOUR_POSITION_TOLERANCE;
+    assert!(spatial_index.update(ball, position));
+    assert_eq!(spatial_index.entities_to_cells.get(&ball), Some(&(0, 0)));
+    // small move keeps neighbours
+    assert!(!spatial_index.update(ball, position + Vec2::new(0.1, 0.0)));
+    let moved_position = Vec2::new(1.1, 0.5) * NEIGHBOUR_POSITION_TOLERANCE;
+    assert!(spatial_index.update(ball, moved_position));
+    assert_eq!(spatial_index.entities_to_cells.get(&ball), Some(&(1, 0)));
+    assert!(!spatial_index.cells.contains_key(&(0, 0)));
+    assert_eq!(spatial_index.cells.get(&(1, 0)), Some(&vec![ball]));
+    assert_eq!(spatial_index.find_near(moved_position), vec![(ball, 0.0)]);
+}
+
+#[test]
+fn spatial_index_removes_ball() {
+    let mut spatial_index = SpatialIndex::default();
+    let ball = Entity::from_raw(1);
+    let neighbour = Entity::from_raw(2);
+    spatial_index.update(ball, Vec2::ZERO);
+    spatial_index.update(
+        neighbour,
+        Vec2::new(NEIGHBOUR_POSITION_TOLERANCE / 2.0, 0.0),
+    );
+    spatial_index.remove(ball);
+    assert!(spatial_index.entities_to_cells.get(&ball).is_none());
+    assert!(spatial_index
+        .entities_to_indexed_positions
+        .get(&ball)
+        .is_none());
+    assert!(spatial_index.find_neighbours(ball).is_empty());
+    assert!(spatial_index.find_neighbours(neighbour).is_empty());
+    spatial_index.remove(neighbour);
+    assert!(spatial_index.cells.is_empty());
+}
+
+#[test]
+fn spatial_index_sorts_neighbours_by_distance() {
+    let mut spatial_index = SpatialIndex::default();
+    let center = Vec2::splat(0.1 * NEIGHBOUR_POSITION_TOLERANCE);
+    let ball = Entity::from_raw(0);
+    // neighbours lie in different cells around the ball, the last one is too far
+    let offsets = [
+        Vec2::new(0.8, 0.0),
+        Vec2::new(-0.3, 0.0),
+        Vec2::new(0.0, -0.6),
+        Vec2::new(1.2, 0.0),
+    ];
+    spatial_index.update(ball, center);
+    for (index, offset) in offsets.iter().enumerate() {
+        spatial_index.update(
+            Entity::from_raw(index as u32 + 1),
+            center + *offset * NEIGHBOUR_POSITION_TOLERANCE,
+        );
+    }
+    let neighbours: Vec<Entity> = spatial_index
+        .find_neighbours(ball)
+        .into_iter()
+        .map(|(neighbour, _)| neighbour)
+        .collect();
+    assert_eq!(
+        neighbours,
+        vec![
+            Entity::from_raw(2),
+            Entity::from_raw(3),
+            Entity::from_raw(1)
+        ]
+    );
+}
+
+#[test]
+fn shipped_levels_are_valid() {
+    let assets_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("assets");
+    for path in LEVEL_PATHS {
+        let bytes = std::fs::read(assets_dir.join(path)).expect("failed to read level");
+        if let Err(err) = LevelAsset::from_bytes(&bytes) {
+            panic!("{} {}", path, err);
+        }
+    }
+}
+
+/// 5 columns and 2 rows, odd row is one ball shorter
+fn broken_level(colors: &str, cols: u8, cells: &str) -> Result<LevelAsset, LevelError> {
+    LevelAsset::from_bytes(
+        format!(
+            r#"(
+                name: "Broken",
+                cols: {},
+                rows: 2,
+                total_rows: 2,
+                colors: [{}],
+                move_down_after: 10,
+                stars: Turns(1, 1),
+                cells: [{}],
+            )"#,
+            cols, colors, cells
+        )
+        .as_bytes(),
+    )
+}
+
+#[test]
+fn unplayable_levels_are_rejected() {
+    assert!(broken_level(
+        "Red",
+        5,
+        "(x: 2, y: 0, species: None), (x: -2, y: -1, species: None)"
+    )
+    .is_ok());
+    assert!(matches!(broken_level("", 5, ""), Err(LevelError::NoColors)));
+    assert!(matches!(
+        broken_level("Stone, Bomb", 5, ""),
+        Err(LevelError::NoColors)
+    ));
+    assert!(matches!(
+        broken_level("Red", 0, ""),
+        Err(LevelError::EmptyBoard)
+    ));
+    for (x, y) in [(3, 0), (-3, 0), (2, -1), (0, -2), (0, 1)] {
+        let cell = format!("(x: {}, y: {}, species: Some(Red))", x, y);
+        assert!(
+            matches!(
+                broken_level("Red", 5, &cell),
+                Err(LevelError::CellOutside(..))
+            ),
+            "{} {}",
+            x,
+            y
+        );
+    }
+    assert!(matches!(
+        broken_level("Red", 5, "(x: 0"),
+        Err(LevelError::Parse(_))
+    ));
+}
+
+/// balls in a row of hexes, adjacent by hex in classic mode and by distance in magnetic one
+fn row_grid(mode: GridMode, row: &[Species]) -> (Grid, Vec<Entity>) {
+    let mut grid = Grid {
+        mode,
+        ..Grid::default()
+    };
+    let entities: Vec<Entity> = (0..row.len())
+        .map(|index| Entity::from_raw(index as u32))
+        .collect();
+    for (index, (entity, species)) in entities.iter().zip(row).enumerate() {
+        let hex = Hex::new(index as i32, 0);
+        let position = grid.layout.hex_to_world_pos(hex);
+        grid.entities_to_positions.insert(*entity, position);
+        grid.entities_to_species.insert(*entity, *species);
+        match mode {
+            GridMode::Classic => grid.update_hex(*entity, hex),
+            GridMode::Magnetic => grid.spatial_index.update(*entity, position),
+        };
+    }
+    grid.refresh_neighbours(&HashSet::from_iter(entities.iter().copied()));
+    (grid, entities)
+}
+
+fn blast_in_row(mode: GridMode, row: &[Species]) -> Vec<usize> {
+    let (grid, entities) = row_grid(mode, row);
+    let bomb_index = row
+        .iter()
+        .position(|species| *species == Species::Bomb)
+        .expect("row has no bomb");
+    let bomb = entities[bomb_index];
+    let blast = find_blast(
+        &grid,
+        bomb,
+        grid.entities_to_positions[&bomb],
+        &grid.entities_to_neighbours[&bomb],
+    );
+    (0..row.len())
+        .filter(|index| blast.contains(&entities[*index]))
+        .collect()
+}
+
+#[test]
+fn bomb_blasts_rings_around_it_whatever_colour() {
+    let rings = BOMB_BLAST_RINGS as usize;
+    let colors = [Species::Red, Species::Blue, Species::Green, Species::Yellow];
+    // one ball out of reach on each side
+    let mut row: Vec<Species> = (0..rings * 2 + 3)
+        .map(|index| colors[index % colors.len()])
+        .collect();
+    row[rings + 1] = Species::Bomb;
+    for mode in [GridMode::Classic, GridMode::Magnetic] {
+        assert_eq!(
+            blast_in_row(mode, &row),
+            (1..=rings * 2 + 1).collect::<Vec<usize>>(),
+            "{}",
+            mode
+        );
+    }
+}
+
+#[test]
+fn bomb_blast_keeps_stones_and_passes_them() {
+    let rings = BOMB_BLAST_RINGS as usize;
+    let mut row = vec![Species::Red; rings + 2];
+    row[0] = Species::Bomb;
+    row[1] = Species::Stone;
+    for mode in [GridMode::Classic, GridMode::Magnetic] {
+        let mut expected: Vec<usize> = (2..=rings).collect();
+        expected.insert(0, 0);
+        assert_eq!(blast_in_row(mode, &row), expected, "{}", mode);
+    }
+}
+
+/// neighbour and species maps built by hand, linked balls touch each other
+struct ClusterMaps {
+    entities: Vec<Entity>,
+    entities_to_neighbours: HashMap<Entity, Vec<(Entity, f32)>>,
+    entities_to_species: HashMap<Entity, Species>,
+}
+
+impl ClusterMaps {
+    fn new(balls: &[Species], links: &[(usize, usize)]) -> Self {
+        let entities: Vec<Entity> = (0..balls.len())
+            .map(|index| Entity::from_raw(index as u32))
+            .collect();
+        let mut entities_to_neighbours: HashMap<Entity, Vec<(Entity, f32)>> = HashMap::default();
+        for (a, b) in links.iter() {
+            for (from, to) in [(a, b), (b, a)] {
+                entities_to_neighbours
+                    .entry(entities[*from])
+                    .or_default()
+                    .push((entities[*to], BALL_DIAMETER));
+            }
+        }
+        Self {
+            entities_to_species: entities
+                .iter()
+                .copied()
+                .zip(balls.iter().copied())
+                .collect(),
+            entities,
+            entities_to_neighbours,
+        }
+    }
+
+    /// indices of clustered balls
+    fn cluster(&self, start_from: usize, frozen: &[usize]) -> Vec<usize> {
+        let frozen: HashSet<Entity> = frozen.iter().map(|index| self.entities[*index]).collect();
+        let (cluster, _) = find_cluster(
+            self.entities[start_from],
+            &self.entities_to_neighbours,
+            &self.entities_to_species,
+            &frozen,
+        );
+        let mut indices: Vec<usize> = (0..self.entities.len())
+            .filter(|index| cluster.contains(&self.entities[*index]))
+            .collect();
+        indices.sort();
+        indices
+    }
+}
+
+#[test]
+fn rainbow_joins_largest_neighbour_group() {
+    use Species::{Blue, Rainbow, Red};
+    // rainbow touches a red pair and a blue triple
+    let maps = ClusterMaps::new(
+        &[Rainbow, Red, Red, Blue, Blue, Blue],
+        &[(0, 1), (1, 2), (0, 3), (3, 4), (4, 5)],
+    );
+    assert_eq!(maps.cluster(0, &[]), vec![0, 3, 4, 5]);
+}
+
+#[test]
+fn rainbow_breaks_ties_by_species_order() {
+    use Species::{Blue, Rainbow, Red};
+    // equal groups give the same pick whatever neighbour comes first
+    for links in [
+        [(0, 1), (1, 2), (0, 3), (3, 4)],
+        [(0, 3), (3, 4), (0, 1), (1, 2)],
+    ] {
+        let maps = ClusterMaps::new(&[Rainbow, Red, Red, Blue, Blue], &links);
+        assert_eq!(maps.cluster(0, &[]), vec![0, 3, 4]);
+    }
+}
+
+#[test]
+fn rainbow_skips_frozen_and_special_neighbours() {
+    use Species::{Bomb, Green, Rainbow, Red, Stone};
+    // red group is behind ice, bombs and stones are larger groups of special species
+    let maps = ClusterMaps::new(
+        &[
+            Rainbow, Red, Red, Red, Red, Bomb, Bomb, Bomb, Stone, Stone, Stone, Green,
+        ],
+        &[
+            (0, 1),
+            (1, 2),
+            (2, 3),
+            (3, 4),
+            (0, 5),
+            (5, 6),
+            (6, 7),
+            (0, 8),
+            (8, 9),
+            (9, 10),
+            (0, 11),
+        ],
+    );
+    assert_eq!(maps.cluster(0, &[1]), vec![0, 11]);
+}
+
+#[test]
+fn stones_never_cluster() {
+    use Species::{Red, Stone};
+    // stone in a line of reds and a line of stones
+    let maps = ClusterMaps::new(
+        &[Red, Stone, Red, Red, Stone, Stone, Stone],
+        &[(0, 1), (1, 2), (2, 3), (0, 4), (4, 5), (5, 6)],
+    );
+    assert_eq!(maps.cluster(0, &[]), vec![0]);
+    assert_eq!(maps.cluster(2, &[]), vec![2, 3]);
+    assert_eq!(maps.cluster(1, &[]), vec![1]);
+    assert_eq!(maps.cluster(4, &[]), vec![4]);
+}
+
+#[test]
+fn stones_are_never_projectiles() {
+    let with_stone: HashSet<Species> = [Species::Stone, Species::Green].into_iter().collect();
+    let only_stone: HashSet<Species> = [Species::Stone].into_iter().collect();
+    for seed in 0..200 {
+        let mut rng = Rng::with_seed(seed);
+        assert_eq!(
+            Species::pick_random(&with_stone, 5, &mut rng),
+            Species::Green
+        );
+        assert!(!Species::pick_random(&only_stone, 5, &mut rng).is_special());
+        for total_colors in 1..=7 {
+            assert!(!Species::random_species(total_colors, &mut rng).is_special());
+        }
+        let grid = Grid {
+            active_species: only_stone.clone(),
+            total_colors: 5,
+            ..Grid::default()
+        };
+        assert!(!grid.pick_species(&mut rng).is_special());
+        assert!(!grid.random_species(&mut rng).is_special());
+    }
+}
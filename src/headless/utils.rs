@@ -0,0 +1,204 @@
+use std::{
+    path::PathBuf,
+    sync::atomic::{AtomicUsize, Ordering},
+    time::Duration,
+};
+
+use bevy::{
+    asset::{AddAsset, AssetPlugin, Assets, Handle},
+    diagnostic::DiagnosticsPlugin,
+    ecs::schedule::ExecutorKind,
+    input::InputPlugin,
+    prelude::{
+        App, AudioSource, HierarchyPlugin, Image, Mesh, NextState, Schedules, TransformPlugin, Vec2,
+    },
+    sprite::ColorMaterial,
+    text::Font,
+    time::TimeUpdateStrategy,
+    MinimalPlugins,
+};
+use bevy_pkv::PkvStore;
+use fastrand::Rng;
+
+use crate::{
+    components::AppState,
+    constants::{
+        FIRE_MODE_ARG, FIRE_MODE_KEY, GRID_MODE_ARG, GRID_MODE_KEY, HEADLESS_ARG, LEVEL_PATHS,
+        SEED_ARG, SHOTS_ARG,
+    },
+    gameplay::{
+        panels::resources::{ScoreCounter, TurnCounter},
+        playfield::resources::Playfield,
+        rng::resources::GameRng,
+        GameplayPlugin,
+    },
+    loading::{
+        audio_assets::AudioAssets,
+        font_assets::FontAssets,
+        level_assets::{LevelAsset, LevelAssets},
+        sprite_assets::SpriteAssets,
+    },
+    ui::resources::{PointerCooldown, UIMenuButtonColors, UIMenuTextColors},
+    utils::{read_arg, read_arg_value},
+};
+
+use super::{
+    constants::{
+        HEADLESS_DEFAULT_SHOTS, HEADLESS_FRAME_TIME, HEADLESS_MAX_FRAMES, HEADLESS_PKV_DIR,
+    },
+    resources::{HeadlessOutcome, ShotScript},
+    HeadlessPlugin,
+};
+
+pub fn has_headless_arg() -> bool {
+    std::env::args().any(|arg| arg == HEADLESS_ARG)
+}
+
+/// every app gets its own settings store, so games running in parallel do not clear each other
+fn headless_pkv_dir() -> PathBuf {
+    static APP_COUNT: AtomicUsize = AtomicUsize::new(0);
+    let app_index = APP_COUNT.fetch_add(1, Ordering::Relaxed);
+    std::env::temp_dir().join(format!(
+        "{}-{}-{}",
+        HEADLESS_PKV_DIR,
+        std::process::id(),
+        app_index
+    ))
+}
+
+/// gameplay without window, renderer and audio, every update advances time by fixed frame
+pub fn build_headless_app(playfield: Playfield, seed: u64, targets: Vec<Vec2>) -> App {
+    // settings are stored aside from the player ones and start from defaults
+    let mut pkv = PkvStore::new_in_dir(headless_pkv_dir());
+    pkv.clear().expect("failed to clear headless settings");
+
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        TransformPlugin,
+        HierarchyPlugin,
+        DiagnosticsPlugin,
+        InputPlugin,
+        AssetPlugin::default(),
+    ))
+    .add_asset::<Mesh>()
+    .add_asset::<ColorMaterial>()
+    .add_asset::<Image>()
+    .add_asset::<Font>()
+    .add_asset::<AudioSource>()
+    .add_asset::<LevelAsset>()
+    .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f32(
+        HEADLESS_FRAME_TIME,
+    )))
+    .insert_resource(pkv)
+    .init_resource::<AudioAssets>()
+    .init_resource::<FontAssets>()
+    .init_resource::<SpriteAssets>()
+    .init_resource::<PointerCooldown>()
+    .init_resource::<UIMenuButtonColors>()
+    .init_resource::<UIMenuTextColors>()
+    .add_plugins((GameplayPlugin, HeadlessPlugin))
+    .add_state::<AppState>()
+    .insert_resource(playfield)
+    .insert_resource(GameRng::new(Some(seed)))
+    .insert_resource(ShotScript::new(targets))
+    .insert_resource(NextState(Some(AppState::GameplayInit)));
+    load_levels(&mut app);
+    app
+}
+
+/// there is no loading state in headless app, so levels are read right away
+fn load_levels(app: &mut App) {
+    let assets_dir = std::env::var("CARGO_MANIFEST_DIR")
+        .map(std::path::PathBuf::from)
+        .unwrap_or_default()
+        .join("assets");
+    let mut levels = app.world.resource_mut::<Assets<LevelAsset>>();
+    let level_assets = LevelAssets {
+        levels: LEVEL_PATHS
+            .iter()
+            .map(|path| {
+                match std::fs::read(assets_dir.join(path))
+                    .map_err(|err| err.to_string())
+                    .and_then(|bytes| LevelAsset::from_bytes(&bytes).map_err(|err| err.to_string()))
+                {
+                    Ok(level) => levels.add(level),
+                    Err(err) => {
+                        println!("Level {} failed to load {}", path, err);
+                        Handle::default()
+                    }
+                }
+            })
+            .collect(),
+    };
+    app.insert_resource(level_assets);
+}
+
+/// run updates until game is over or won, or frames limit is reached
+pub fn simulate_headless_game(app: &mut App, max_frames: u32) -> HeadlessOutcome {
+    app.finish();
+    app.cleanup();
+    // systems without explicit order keep the same order every run, so seed replays the game
+    for (_, schedule) in app.world.resource_mut::<Schedules>().iter_mut() {
+        schedule.set_executor_kind(ExecutorKind::SingleThreaded);
+    }
+    for _ in 0..max_frames {
+        app.update();
+        let mut outcome = app.world.resource_mut::<HeadlessOutcome>();
+        outcome.frames += 1;
+        if outcome.state.is_some() {
+            break;
+        }
+    }
+    let score = app.world.resource::<ScoreCounter>().0;
+    let turns = app.world.resource::<TurnCounter>().0;
+    let mut outcome = app.world.resource_mut::<HeadlessOutcome>();
+    outcome.score = score;
+    outcome.turns = turns;
+    outcome.clone()
+}
+
+/// aim somewhere into upper half of the playfield
+pub fn random_targets(playfield: &Playfield, seed: u64, shots: u32) -> Vec<Vec2> {
+    let mut rng = Rng::with_seed(seed);
+    (0..shots)
+        .map(|_| {
+            Vec2::new(
+                (rng.f32() - 0.5) * playfield.width,
+                rng.f32() * playfield.height / 2.0,
+            )
+        })
+        .collect()
+}
+
+pub fn run_headless() {
+    let seed = read_arg::<u64>(SEED_ARG).unwrap_or_else(|| fastrand::u64(..));
+    let shots = read_arg::<u32>(SHOTS_ARG).unwrap_or(HEADLESS_DEFAULT_SHOTS);
+    let playfield = Playfield::default();
+    let targets = random_targets(&playfield, seed, shots);
+
+    let mut app = build_headless_app(playfield, seed, targets);
+    if let Some(grid_mode) = read_arg_value(GRID_MODE_ARG) {
+        app.world
+            .resource_mut::<PkvStore>()
+            .set_string(GRID_MODE_KEY, &grid_mode)
+            .expect("failed to save grid mode");
+    }
+    if let Some(fire_mode) = read_arg_value(FIRE_MODE_ARG) {
+        app.world
+            .resource_mut::<PkvStore>()
+            .set_string(FIRE_MODE_KEY, &fire_mode)
+            .expect("failed to save fire mode");
+    }
+    let outcome = simulate_headless_game(&mut app, HEADLESS_MAX_FRAMES);
+    println!(
+        "Headless game seed {} outcome {:?} score {} turns {} shots {} bounces {} frames {}",
+        seed,
+        outcome.state,
+        outcome.score,
+        outcome.turns,
+        outcome.shots,
+        outcome.bounces,
+        outcome.frames
+    );
+}
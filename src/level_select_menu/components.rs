@@ -0,0 +1,9 @@
+use bevy::prelude::Component;
+
+use crate::ui::resources::ColorType;
+
+#[derive(Component)]
+pub struct PageButton {
+    pub color_type: ColorType,
+    pub delta: i32,
+}
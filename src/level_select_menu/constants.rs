@@ -0,0 +1,3 @@
+pub const LEVEL_TILES_PER_ROW: usize = 3;
+pub const LEVEL_TILE_ROWS_PER_PAGE: usize = 3;
+pub const LEVEL_TILES_PER_PAGE: usize = LEVEL_TILES_PER_ROW * LEVEL_TILE_ROWS_PER_PAGE;
@@ -0,0 +1,38 @@
+use bevy::prelude::{in_state, App, IntoSystemConfigs, OnEnter, OnExit, Plugin, Update};
+
+use crate::{
+    components::AppState,
+    gameplay::level::systems::interact_with_start_level_button,
+    ui::systems::{cleanup_menu, interact_with_next_state_button},
+};
+
+use self::{
+    resources::LevelSelectPage,
+    systems::{build_level_page, flip_level_page, keydown_detect, setup_menu},
+};
+
+mod components;
+mod constants;
+mod resources;
+mod systems;
+
+pub struct LevelSelectMenuPlugin;
+
+impl Plugin for LevelSelectMenuPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<LevelSelectPage>()
+            .add_systems(OnEnter(AppState::LevelSelect), setup_menu)
+            .add_systems(
+                Update,
+                (
+                    interact_with_next_state_button,
+                    interact_with_start_level_button,
+                    keydown_detect,
+                    flip_level_page,
+                    build_level_page.after(flip_level_page),
+                )
+                    .run_if(in_state(AppState::LevelSelect)),
+            )
+            .add_systems(OnExit(AppState::LevelSelect), cleanup_menu);
+    }
+}
@@ -0,0 +1,20 @@
+use bevy::prelude::Resource;
+
+use super::constants::LEVEL_TILES_PER_PAGE;
+
+#[derive(Resource, Default)]
+pub struct LevelSelectPage {
+    pub page: usize,
+    pub total_levels: usize,
+}
+
+impl LevelSelectPage {
+    pub fn total_pages(&self) -> usize {
+        self.total_levels.div_ceil(LEVEL_TILES_PER_PAGE).max(1)
+    }
+
+    /// stays inside first and last pages
+    pub fn flipped(&self, delta: i32) -> usize {
+        (self.page as i32 + delta).clamp(0, self.total_pages() as i32 - 1) as usize
+    }
+}
@@ -0,0 +1,222 @@
+use bevy::{
+    input::mouse::MouseWheel,
+    prelude::{
+        Changed, Commands, DespawnRecursiveExt, DetectChanges, Entity, EventReader, Input, KeyCode,
+        NextState, Query, Res, ResMut, With,
+    },
+    ui::{BackgroundColor, Interaction},
+};
+use bevy_pkv::PkvStore;
+
+use crate::{
+    components::AppState,
+    gameplay::level::{
+        components::StartLevelButton,
+        utils::{find_campaign_level, is_level_unlocked, read_level_stars},
+    },
+    loading::{font_assets::FontAssets, level_assets::LevelAssets},
+    ui::{
+        components::{NextStateButton, NoneComponent, UIMenu},
+        resources::{ColorType, PointerCooldown, UIMenuButtonColors, UIMenuTextColors},
+        utils::{
+            button_utils::{append_middle_text_button, button_color_by_interaction},
+            camera_utils::build_ui_camera,
+            flex_utils::append_flex_row_evenly,
+            menu_utils::build_menu,
+            text_utils::{append_large_text, append_middle_text},
+        },
+    },
+};
+
+use super::{
+    components::PageButton,
+    constants::{LEVEL_TILES_PER_PAGE, LEVEL_TILES_PER_ROW},
+    resources::LevelSelectPage,
+};
+
+/// menu itself is built by build_level_page when page is set
+pub fn setup_menu(
+    mut commands: Commands,
+    mut level_select_page: ResMut<LevelSelectPage>,
+    level_assets: Res<LevelAssets>,
+    pkv: Res<PkvStore>,
+) {
+    build_ui_camera(&mut commands);
+    let total_levels = level_assets.levels.len();
+    // open page with current campaign level
+    *level_select_page = LevelSelectPage {
+        page: find_campaign_level(&pkv, total_levels) / LEVEL_TILES_PER_PAGE,
+        total_levels,
+    };
+}
+
+pub fn build_level_page(
+    mut commands: Commands,
+    level_select_page: Res<LevelSelectPage>,
+    menu_query: Query<Entity, With<UIMenu>>,
+    font_assets: Res<FontAssets>,
+    button_colors: Res<UIMenuButtonColors>,
+    text_colors: Res<UIMenuTextColors>,
+    pkv: Res<PkvStore>,
+) {
+    if !level_select_page.is_changed() {
+        return;
+    }
+    for menu_entity in menu_query.iter() {
+        commands.entity(menu_entity).despawn_recursive();
+    }
+    let first_level = level_select_page.page * LEVEL_TILES_PER_PAGE;
+    let last_level = (first_level + LEVEL_TILES_PER_PAGE).min(level_select_page.total_levels);
+    let page_levels: Vec<usize> = (first_level..last_level).collect();
+    build_menu(&mut commands, |parent| {
+        append_large_text(
+            parent,
+            "Уровни",
+            &font_assets,
+            &text_colors,
+            None::<NoneComponent>,
+        );
+        for row_levels in page_levels.chunks(LEVEL_TILES_PER_ROW) {
+            append_flex_row_evenly(parent, |parent| {
+                for level in row_levels.iter() {
+                    match is_level_unlocked(&pkv, *level) {
+                        true => {
+                            let stars = read_level_stars(&pkv, *level) as usize;
+                            append_middle_text_button(
+                                parent,
+                                Some(StartLevelButton {
+                                    color_type: ColorType::Green,
+                                    some_level: Some(*level),
+                                }),
+                                &ColorType::Green,
+                                format!("{}\n{:-<3}", level + 1, "*".repeat(stars)).as_str(),
+                                &font_assets,
+                                &text_colors,
+                                &button_colors,
+                                false,
+                            );
+                        }
+                        false => {
+                            append_middle_text_button(
+                                parent,
+                                None::<NoneComponent>,
+                                &ColorType::Gray,
+                                format!("{}\nзакрыт", level + 1).as_str(),
+                                &font_assets,
+                                &text_colors,
+                                &button_colors,
+                                true,
+                            );
+                        }
+                    }
+                }
+            });
+        }
+        append_flex_row_evenly(parent, |parent| {
+            append_middle_text_button(
+                parent,
+                Some(PageButton {
+                    color_type: ColorType::Blue,
+                    delta: -1,
+                }),
+                &ColorType::Blue,
+                "‹",
+                &font_assets,
+                &text_colors,
+                &button_colors,
+                false,
+            );
+            append_middle_text(
+                parent,
+                format!(
+                    "{} / {}",
+                    level_select_page.page + 1,
+                    level_select_page.total_pages()
+                )
+                .as_str(),
+                &font_assets,
+                &text_colors,
+                None::<NoneComponent>,
+            );
+            append_middle_text_button(
+                parent,
+                Some(PageButton {
+                    color_type: ColorType::Blue,
+                    delta: 1,
+                }),
+                &ColorType::Blue,
+                "›",
+                &font_assets,
+                &text_colors,
+                &button_colors,
+                false,
+            );
+        });
+        append_middle_text_button(
+            parent,
+            Some(NextStateButton {
+                color_type: ColorType::Gray,
+                next_state: AppState::StartMenu,
+            }),
+            &ColorType::Gray,
+            "Главное меню",
+            &font_assets,
+            &text_colors,
+            &button_colors,
+            false,
+        );
+    });
+}
+
+/// pages are flipped by buttons, mouse wheel and arrow keys
+pub fn flip_level_page(
+    button_colors: Res<UIMenuButtonColors>,
+    mut interaction_query: Query<
+        (&Interaction, &mut BackgroundColor, &PageButton),
+        Changed<Interaction>,
+    >,
+    mut mouse_wheel_events: EventReader<MouseWheel>,
+    keyboard_input_key_code: Res<Input<KeyCode>>,
+    mut level_select_page: ResMut<LevelSelectPage>,
+    mut pointer_cooldown: ResMut<PointerCooldown>,
+) {
+    let mut delta = 0;
+    for (interaction, mut background_color, page_button) in &mut interaction_query {
+        *background_color = button_color_by_interaction(
+            false,
+            &button_colors,
+            &page_button.color_type,
+            interaction,
+        )
+        .into();
+        if *interaction == Interaction::Pressed {
+            pointer_cooldown.started = true;
+            delta += page_button.delta;
+        }
+    }
+    // scroll down shows next page, one page per frame for touchpad
+    let scroll: f32 = mouse_wheel_events.iter().map(|event| event.y).sum();
+    if scroll != 0.0 {
+        delta -= scroll.signum() as i32;
+    }
+    if keyboard_input_key_code.any_just_released([KeyCode::Left]) {
+        delta -= 1;
+    }
+    if keyboard_input_key_code.any_just_released([KeyCode::Right]) {
+        delta += 1;
+    }
+    let page = level_select_page.flipped(delta);
+    // page is rebuilt on change only
+    if page != level_select_page.page {
+        level_select_page.page = page;
+    }
+}
+
+pub fn keydown_detect(
+    mut app_state_next_state: ResMut<NextState<AppState>>,
+    keyboard_input_key_code: Res<Input<KeyCode>>,
+) {
+    if keyboard_input_key_code.any_just_released([KeyCode::Escape]) {
+        app_state_next_state.set(AppState::StartMenu);
+    }
+}
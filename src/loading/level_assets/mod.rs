@@ -0,0 +1,46 @@
+mod loader;
+mod resources;
+
+use bevy::{
+    asset::AddAsset,
+    prelude::{App, AssetServer, Commands, Plugin, ResMut, Startup},
+};
+
+use crate::constants::LEVEL_PATHS;
+
+use self::loader::LevelAssetLoader;
+#[cfg(test)]
+pub use self::resources::LevelError;
+pub use self::resources::{LevelAsset, LevelAssets, LevelObjective};
+
+use super::resources::AssetsLoading;
+
+pub struct LevelAssetsPlugin;
+
+impl Plugin for LevelAssetsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_asset::<LevelAsset>()
+            .init_asset_loader::<LevelAssetLoader>()
+            .init_resource::<LevelAssets>()
+            .add_systems(Startup, load_assets);
+    }
+}
+
+pub fn load_assets(
+    mut commands: Commands,
+    asset_server: ResMut<AssetServer>,
+    mut loading: ResMut<AssetsLoading>,
+) {
+    let assets = LevelAssets {
+        levels: LEVEL_PATHS
+            .iter()
+            .map(|path| asset_server.load(*path))
+            .collect(),
+    };
+
+    for level in assets.levels.iter() {
+        loading.0.push(level.clone_weak_untyped());
+    }
+
+    commands.insert_resource(assets);
+}
@@ -0,0 +1,235 @@
+use std::{
+    error::Error,
+    fmt::{self, Display, Formatter},
+};
+
+use bevy::{
+    prelude::{Handle, Resource},
+    reflect::{TypePath, TypeUuid},
+};
+use serde::Deserialize;
+
+use crate::gameplay::{ball::components::Species, level::resources::ObjectiveProgress};
+
+#[derive(Resource, Debug, Default)]
+pub struct LevelAssets {
+    pub levels: Vec<Handle<LevelAsset>>,
+}
+
+/// x is column from -cols / 2 to cols / 2, y is row from 0 (bottom) to -(rows - 1) (top)
+#[derive(Deserialize, Debug, Clone)]
+pub struct LevelCell {
+    pub x: i32,
+    pub y: i32,
+    /// None keeps cell empty
+    pub species: Option<Species>,
+    /// ice layers over the ball, pops next to it crack them one by one
+    #[serde(default)]
+    pub ice: u8,
+    /// ball takes the next level colour every turn
+    #[serde(default)]
+    pub chameleon: bool,
+    /// level is won when all collectibles are freed
+    #[serde(default)]
+    pub collectible: bool,
+}
+
+/// thresholds for the second and the third star, the first one is given for win
+#[derive(Deserialize, Debug, Clone, Copy)]
+pub enum LevelStars {
+    /// minimal score
+    Score(u32, u32),
+    /// maximal turns
+    Turns(u32, u32),
+}
+
+impl LevelStars {
+    pub fn count(&self, score: u32, turns: u32) -> u8 {
+        let (second, third) = match *self {
+            LevelStars::Score(second, third) => (score >= second, score >= third),
+            LevelStars::Turns(second, third) => (turns <= second, turns <= third),
+        };
+        1 + second as u8 + third as u8
+    }
+}
+
+/// level is won when objective is met, objectives are combined with All and Any
+#[derive(Deserialize, Debug, Clone, Default, PartialEq)]
+pub enum LevelObjective {
+    /// no balls left except stones
+    #[default]
+    ClearBoard,
+    /// no balls left from the top row of generated grid except stones
+    ClearTopRow,
+    /// minimal score
+    Score(u32),
+    /// balls of species popped in colour clusters, dropped, blasted and burnt ones are not counted,
+    /// rainbow counts as the colour of its cluster
+    Pop(Species, u32),
+    /// every collectible is popped or dropped
+    FreeCollectibles,
+    All(Vec<LevelObjective>),
+    Any(Vec<LevelObjective>),
+}
+
+impl LevelObjective {
+    pub fn is_met(&self, progress: &ObjectiveProgress) -> bool {
+        match self {
+            LevelObjective::ClearBoard => progress.is_board_cleared,
+            LevelObjective::ClearTopRow => progress.is_top_row_cleared,
+            LevelObjective::Score(score) => progress.score >= *score,
+            LevelObjective::Pop(species, count) => progress.popped(species) >= *count,
+            LevelObjective::FreeCollectibles => {
+                progress.collectibles_total > 0 && progress.collectibles_left == 0
+            }
+            LevelObjective::All(objectives) => objectives
+                .iter()
+                .all(|objective| objective.is_met(progress)),
+            LevelObjective::Any(objectives) => objectives
+                .iter()
+                .any(|objective| objective.is_met(progress)),
+        }
+    }
+
+    /// progress line for HUD, nested groups are put in brackets
+    pub fn describe(&self, progress: &ObjectiveProgress) -> String {
+        match self {
+            LevelObjective::ClearBoard => "очистить поле".to_string(),
+            LevelObjective::ClearTopRow => "очистить верхний ряд".to_string(),
+            LevelObjective::Score(score) => {
+                format!("очки {}/{}", progress.score.min(*score), score)
+            }
+            LevelObjective::Pop(species, count) => format!(
+                "{} {}/{}",
+                species.title(),
+                progress.popped(species).min(*count),
+                count
+            ),
+            LevelObjective::FreeCollectibles => format!(
+                "сокровища {}/{}",
+                progress.collectibles_total - progress.collectibles_left,
+                progress.collectibles_total
+            ),
+            LevelObjective::All(objectives) => Self::describe_group(objectives, " и ", progress),
+            LevelObjective::Any(objectives) => Self::describe_group(objectives, " или ", progress),
+        }
+    }
+
+    fn describe_group(
+        objectives: &[LevelObjective],
+        separator: &str,
+        progress: &ObjectiveProgress,
+    ) -> String {
+        objectives
+            .iter()
+            .map(|objective| match objective {
+                LevelObjective::All(_) | LevelObjective::Any(_) => {
+                    format!("({})", objective.describe(progress))
+                }
+                _ => objective.describe(progress),
+            })
+            .collect::<Vec<String>>()
+            .join(separator)
+    }
+
+    /// single objectives which are met, groups are flattened
+    pub fn met_objectives(&self, progress: &ObjectiveProgress) -> Vec<String> {
+        match self {
+            LevelObjective::All(objectives) | LevelObjective::Any(objectives) => objectives
+                .iter()
+                .flat_map(|objective| objective.met_objectives(progress))
+                .collect(),
+            _ if self.is_met(progress) => vec![self.describe(progress)],
+            _ => vec![],
+        }
+    }
+}
+
+#[derive(Deserialize, TypeUuid, TypePath, Debug, Clone)]
+#[uuid = "5d3b9a2e-7c41-4f0e-9b6a-1e8f2c7d4a90"]
+pub struct LevelAsset {
+    pub name: String,
+    pub cols: u8,
+    /// rows on the board at start
+    pub rows: u8,
+    /// rows on the board plus rows spawned on move down
+    pub total_rows: u8,
+    pub colors: Vec<Species>,
+    /// chance of stone for cells not listed here, stones also can be listed in cells
+    #[serde(default)]
+    pub stone_chance: f32,
+    /// chance of frozen ball for cells not listed here
+    #[serde(default)]
+    pub ice_chance: f32,
+    /// chance of chameleon ball for cells not listed here
+    #[serde(default)]
+    pub chameleon_chance: f32,
+    pub move_down_after: u8,
+    pub stars: LevelStars,
+    /// board has to be cleared or collectibles freed when there is no objective
+    #[serde(default)]
+    pub objective: Option<LevelObjective>,
+    /// cells not listed here get random species from colors
+    pub cells: Vec<LevelCell>,
+}
+
+impl LevelAsset {
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, LevelError> {
+        let level = ron::de::from_bytes::<LevelAsset>(bytes)?;
+        level.validate()?;
+        Ok(level)
+    }
+
+    /// board has to be generated and every generated ball needs a colour
+    fn validate(&self) -> Result<(), LevelError> {
+        if !self.colors.iter().any(|species| !species.is_special()) {
+            return Err(LevelError::NoColors);
+        }
+        if self.cols == 0 || self.rows == 0 {
+            return Err(LevelError::EmptyBoard);
+        }
+        match self.cells.iter().find(|cell| !self.is_on_board(cell)) {
+            Some(cell) => Err(LevelError::CellOutside(cell.x, cell.y)),
+            None => Ok(()),
+        }
+    }
+
+    /// odd rows are one ball shorter on the right side, as the grid is generated
+    fn is_on_board(&self, cell: &LevelCell) -> bool {
+        let max_side_x = (self.cols / 2) as i32;
+        let max_x = match cell.y % 2 == 0 {
+            true => max_side_x,
+            false => max_side_x - 1,
+        };
+        (-max_side_x..=max_x).contains(&cell.x) && (-(self.rows as i32) + 1..=0).contains(&cell.y)
+    }
+}
+
+/// level file which can not be played
+#[derive(Debug)]
+pub enum LevelError {
+    Parse(ron::error::SpannedError),
+    /// colours are empty or special species only
+    NoColors,
+    EmptyBoard,
+    CellOutside(i32, i32),
+}
+
+impl Display for LevelError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            LevelError::Parse(err) => write!(f, "{}", err),
+            LevelError::NoColors => write!(f, "level has no colour except special species"),
+            LevelError::EmptyBoard => write!(f, "level has no columns or rows"),
+            LevelError::CellOutside(x, y) => write!(f, "cell x {} y {} is outside board", x, y),
+        }
+    }
+}
+
+impl Error for LevelError {}
+
+impl From<ron::error::SpannedError> for LevelError {
+    fn from(err: ron::error::SpannedError) -> Self {
+        LevelError::Parse(err)
+    }
+}
@@ -1,12 +1,14 @@
 use bevy::prelude::{App, Plugin, Update};
 
 use self::{
-    audio_assets::AudioAssetsPlugin, font_assets::FontAssetsPlugin, resources::AssetsLoading,
-    sprite_assets::SpriteAssetsPlugin, systems::check_assets_ready,
+    audio_assets::AudioAssetsPlugin, font_assets::FontAssetsPlugin,
+    level_assets::LevelAssetsPlugin, resources::AssetsLoading, sprite_assets::SpriteAssetsPlugin,
+    systems::check_assets_ready,
 };
 
 pub mod audio_assets;
 pub mod font_assets;
+pub mod level_assets;
 mod resources;
 pub mod sprite_assets;
 mod systems;
@@ -16,7 +18,12 @@ pub struct LoadingPlugin;
 impl Plugin for LoadingPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<AssetsLoading>()
-            .add_plugins((AudioAssetsPlugin, FontAssetsPlugin, SpriteAssetsPlugin))
+            .add_plugins((
+                AudioAssetsPlugin,
+                FontAssetsPlugin,
+                SpriteAssetsPlugin,
+                LevelAssetsPlugin,
+            ))
             .add_systems(Update, check_assets_ready);
     }
 }
@@ -7,7 +7,12 @@ use components::AppState;
 use game_audio::GameAudioPlugin;
 use game_over_menu::GameOverMenuPlugin;
 use game_win_menu::GameWinMenuPlugin;
-use gameplay::GameplayPlugin;
+use gameplay::{
+    constants::{PLAYFIELD_HEIGHT, PLAYFIELD_WIDTH},
+    GameplayPlugin,
+};
+use headless::utils::{has_headless_arg, run_headless};
+use level_select_menu::LevelSelectMenuPlugin;
 use loading::LoadingPlugin;
 use settings_menu::SettingsMenuPlugin;
 use start_menu::StartMenuPlugin;
@@ -19,17 +24,24 @@ mod game_audio;
 mod game_over_menu;
 mod game_win_menu;
 mod gameplay;
+mod headless;
+mod level_select_menu;
 mod loading;
 mod settings_menu;
 mod start_menu;
 mod ui;
+mod utils;
 fn main() {
+    if has_headless_arg() {
+        run_headless();
+        return;
+    }
     App::new()
         .add_plugins((
             DefaultPlugins.set(WindowPlugin {
                 primary_window: Some(Window {
                     title: "Bevy+WASM Ball Shooter".into(),
-                    resolution: (408., 755.).into(),
+                    resolution: (PLAYFIELD_WIDTH, PLAYFIELD_HEIGHT).into(),
                     fit_canvas_to_parent: true,
                     prevent_default_event_handling: false,
                     ..default()
@@ -40,6 +52,7 @@ fn main() {
             GameAudioPlugin,
             UIPlugin,
             StartMenuPlugin,
+            LevelSelectMenuPlugin,
             SettingsMenuPlugin,
             GameWinMenuPlugin,
             GameplayPlugin,
@@ -1,6 +1,21 @@
 use bevy::prelude::Component;
+use bevy_pkv::PkvStore;
 
-use crate::ui::resources::ColorType;
+use crate::{
+    gameplay::{ball::resources::FireMode, grid::resources::GridMode},
+    settings_menu::utils::seed_utils::read_seed,
+    ui::resources::ColorType,
+};
+
+/// Button of a settings line where only one option with the same key is pressed
+pub trait OptionButton: Component {
+    fn key(&self) -> &str;
+    fn is_pressed(&self) -> bool;
+    fn set_pressed(&mut self, pressed: bool);
+    fn color_type(&self) -> &ColorType;
+    /// Value stored under the key when the button gets pressed
+    fn stored_value(&self, pkv: &PkvStore) -> String;
+}
 
 #[derive(Component)]
 pub struct VolumeButton {
@@ -55,3 +70,110 @@ pub struct MoveDownButton {
     pub pressed: bool,
     pub color_type: ColorType,
 }
+
+#[derive(Component)]
+pub struct QueueDepthButton {
+    pub value: u8,
+    pub key: String,
+    pub pressed: bool,
+    pub color_type: ColorType,
+}
+
+#[derive(Component)]
+pub struct SeedButton {
+    pub fixed: bool,
+    pub key: String,
+    pub last_key: String,
+    pub pressed: bool,
+    pub color_type: ColorType,
+}
+
+#[derive(Component)]
+pub struct SeedText {}
+
+#[derive(Component)]
+pub struct GridModeButton {
+    pub value: GridMode,
+    pub key: String,
+    pub pressed: bool,
+    pub color_type: ColorType,
+}
+
+#[derive(Component)]
+pub struct FireModeButton {
+    pub value: FireMode,
+    pub key: String,
+    pub pressed: bool,
+    pub color_type: ColorType,
+}
+
+#[derive(Component)]
+pub struct PopHintButton {
+    pub value: bool,
+    pub key: String,
+    pub pressed: bool,
+    pub color_type: ColorType,
+}
+
+/// buttons which store their value as it is shown
+macro_rules! impl_option_button {
+    ($($button:ty),*) => {
+        $(
+            impl OptionButton for $button {
+                fn key(&self) -> &str {
+                    &self.key
+                }
+
+                fn is_pressed(&self) -> bool {
+                    self.pressed
+                }
+
+                fn set_pressed(&mut self, pressed: bool) {
+                    self.pressed = pressed;
+                }
+
+                fn color_type(&self) -> &ColorType {
+                    &self.color_type
+                }
+
+                fn stored_value(&self, _pkv: &PkvStore) -> String {
+                    self.value.to_string()
+                }
+            }
+        )*
+    };
+}
+
+impl_option_button!(
+    QueueDepthButton,
+    GridModeButton,
+    FireModeButton,
+    PopHintButton
+);
+
+/// fixed seed stores the last played one, so the game can be replayed
+impl OptionButton for SeedButton {
+    fn key(&self) -> &str {
+        &self.key
+    }
+
+    fn is_pressed(&self) -> bool {
+        self.pressed
+    }
+
+    fn set_pressed(&mut self, pressed: bool) {
+        self.pressed = pressed;
+    }
+
+    fn color_type(&self) -> &ColorType {
+        &self.color_type
+    }
+
+    fn stored_value(&self, pkv: &PkvStore) -> String {
+        let seed = match self.fixed {
+            true => read_seed(&self.last_key, pkv).unwrap_or(0),
+            false => 0,
+        };
+        seed.to_string()
+    }
+}
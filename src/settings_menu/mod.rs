@@ -5,16 +5,21 @@ use crate::{
     ui::systems::{cleanup_menu, interact_with_next_state_button},
 };
 
-use self::systems::{
-    colors_systems::interact_with_colors_button,
-    columns_systems::interact_with_columns_button,
-    keydown_systems::keydown_detect,
-    menu_systems::setup_menu,
-    move_down_systems::interact_with_move_down_button,
-    rows_systems::{
-        interact_with_init_rows_button, interact_with_total_rows_button, update_rows_text,
+use self::{
+    components::{FireModeButton, GridModeButton, PopHintButton, QueueDepthButton, SeedButton},
+    systems::{
+        colors_systems::interact_with_colors_button,
+        columns_systems::interact_with_columns_button,
+        keydown_systems::keydown_detect,
+        menu_systems::setup_menu,
+        move_down_systems::interact_with_move_down_button,
+        option_systems::interact_with_option_button,
+        rows_systems::{
+            interact_with_init_rows_button, interact_with_total_rows_button, update_rows_text,
+        },
+        seed_systems::update_seed_text,
+        volume_systems::interact_with_volume_button,
     },
-    volume_systems::interact_with_volume_button,
 };
 
 mod components;
@@ -36,7 +41,13 @@ impl Plugin for SettingsMenuPlugin {
                     interact_with_init_rows_button,
                     interact_with_total_rows_button,
                     interact_with_move_down_button,
+                    interact_with_option_button::<QueueDepthButton>,
+                    interact_with_option_button::<GridModeButton>,
+                    interact_with_option_button::<FireModeButton>,
+                    interact_with_option_button::<PopHintButton>,
                     update_rows_text,
+                    interact_with_option_button::<SeedButton>,
+                    update_seed_text,
                 )
                     .run_if(in_state(AppState::Settings)),
             )
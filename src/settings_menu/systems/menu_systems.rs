@@ -4,14 +4,17 @@ use bevy_pkv::PkvStore;
 use crate::{
     components::AppState,
     constants::{
-        INIT_ROWS_KEY, MOVE_DOWN_AFTER_KEY, TOTAL_COLORS_KEY, TOTAL_COLUMNS_KEY, TOTAL_ROWS_KEY,
+        FIRE_MODE_KEY, GRID_MODE_KEY, INIT_ROWS_KEY, MOVE_DOWN_AFTER_KEY, POP_HINT_KEY,
+        QUEUE_DEPTH_KEY, SEED_KEY, TOTAL_COLORS_KEY, TOTAL_COLUMNS_KEY, TOTAL_ROWS_KEY,
     },
     game_audio::constants::{MAIN_SOUND_VOLUME_KEY, SFX_SOUND_VOLUME_KEY},
     loading::font_assets::FontAssets,
     settings_menu::utils::{
         colors_utils::build_colors_line, columns_utils::build_columns_line,
-        move_down_utils::build_move_down_line, rows_utils::build_rows_line,
-        volume_utils::build_volume_line,
+        fire_mode_utils::build_fire_mode_line, grid_mode_utils::build_grid_mode_line,
+        move_down_utils::build_move_down_line, pop_hint_utils::build_pop_hint_line,
+        queue_depth_utils::build_queue_depth_line, rows_utils::build_rows_line,
+        seed_utils::build_seed_line, volume_utils::build_volume_line,
     },
     ui::{
         components::{NextStateButton, NoneComponent},
@@ -95,6 +98,51 @@ pub fn setup_menu(
             &text_colors,
             &pkv,
         );
+        build_queue_depth_line(
+            "Шаров в очереди",
+            QUEUE_DEPTH_KEY,
+            parent,
+            &font_assets,
+            &button_colors,
+            &text_colors,
+            &pkv,
+        );
+        build_grid_mode_line(
+            "Режим сетки",
+            GRID_MODE_KEY,
+            parent,
+            &font_assets,
+            &button_colors,
+            &text_colors,
+            &pkv,
+        );
+        build_fire_mode_line(
+            "Стрельба",
+            FIRE_MODE_KEY,
+            parent,
+            &font_assets,
+            &button_colors,
+            &text_colors,
+            &pkv,
+        );
+        build_pop_hint_line(
+            "Подсказка",
+            POP_HINT_KEY,
+            parent,
+            &font_assets,
+            &button_colors,
+            &text_colors,
+            &pkv,
+        );
+        build_seed_line(
+            "Сид",
+            SEED_KEY,
+            parent,
+            &font_assets,
+            &button_colors,
+            &text_colors,
+            &pkv,
+        );
         append_middle_text_button(
             parent,
             Some(NextStateButton {
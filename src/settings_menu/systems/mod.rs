@@ -3,5 +3,7 @@ pub mod columns_systems;
 pub mod keydown_systems;
 pub mod menu_systems;
 pub mod move_down_systems;
+pub mod option_systems;
 pub mod rows_systems;
+pub mod seed_systems;
 pub mod volume_systems;
@@ -0,0 +1,52 @@
+use bevy::{
+    prelude::{Query, Res, ResMut},
+    ui::{BackgroundColor, Interaction},
+};
+use bevy_pkv::PkvStore;
+
+use crate::{
+    settings_menu::components::OptionButton,
+    ui::{resources::UIMenuButtonColors, utils::button_utils::button_color_by_interaction},
+};
+
+pub fn interact_with_option_button<T: OptionButton>(
+    button_colors: Res<UIMenuButtonColors>,
+    mut button_query: Query<(&Interaction, &mut BackgroundColor, &mut T)>,
+    mut pkv: ResMut<PkvStore>,
+) {
+    let pressed_button: (i32, String) = match button_query
+        .iter()
+        .enumerate()
+        .find(|(_, (interaction, _, _))| **interaction == Interaction::Pressed)
+    {
+        Some((idx, (_, _, pressed_option))) => (idx as i32, pressed_option.key().to_string()),
+        None => (-1, "".to_string()),
+    };
+    for (idx, (interaction, mut background_color, mut option_button)) in
+        button_query.iter_mut().enumerate()
+    {
+        match *interaction {
+            Interaction::Pressed if !option_button.is_pressed() => {
+                option_button.set_pressed(true);
+                let value = option_button.stored_value(pkv.as_ref());
+                pkv.set_string(option_button.key(), &value)
+                    .expect("failed to save option");
+            }
+            Interaction::None
+                if pressed_button.0 > -1
+                    && pressed_button.0 != idx as i32
+                    && pressed_button.1 == option_button.key() =>
+            {
+                option_button.set_pressed(false);
+            }
+            _ => {}
+        };
+        *background_color = button_color_by_interaction(
+            option_button.is_pressed(),
+            &button_colors,
+            option_button.color_type(),
+            interaction,
+        )
+        .into();
+    }
+}
@@ -0,0 +1,19 @@
+use bevy::{
+    prelude::{Query, Res, With},
+    text::Text,
+};
+use bevy_pkv::PkvStore;
+
+use crate::{
+    constants::SEED_KEY,
+    settings_menu::{
+        components::SeedText,
+        utils::seed_utils::{format_seed, read_seed},
+    },
+};
+
+pub fn update_seed_text(pkv: Res<PkvStore>, mut seed_text_query: Query<&mut Text, With<SeedText>>) {
+    for mut seed_text in &mut seed_text_query {
+        seed_text.sections[0].value = format_seed(read_seed(SEED_KEY, pkv.as_ref()));
+    }
+}
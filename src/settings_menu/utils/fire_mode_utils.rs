@@ -0,0 +1,70 @@
+use bevy::prelude::{ChildBuilder, Res};
+use bevy_pkv::PkvStore;
+
+use crate::{
+    gameplay::ball::resources::FireMode,
+    loading::font_assets::FontAssets,
+    settings_menu::components::FireModeButton,
+    ui::{
+        components::NoneComponent,
+        resources::{ColorType, UIMenuButtonColors, UIMenuTextColors},
+        utils::{
+            button_utils::append_middle_text_button,
+            flex_utils::{append_flex_column_start, append_flex_row_evenly},
+            text_utils::append_middle_text,
+        },
+    },
+};
+
+pub fn read_fire_mode(key: &str, pkv: &Res<PkvStore>) -> FireMode {
+    match pkv.get::<String>(key) {
+        Ok(fire_mode) => FireMode::from(fire_mode.as_str()),
+        Err(_) => FireMode::default(),
+    }
+}
+
+pub fn build_fire_mode_line(
+    title: &str,
+    key: &str,
+    parent: &mut ChildBuilder<'_, '_, '_>,
+    font_assets: &Res<FontAssets>,
+    button_colors: &Res<UIMenuButtonColors>,
+    text_colors: &Res<UIMenuTextColors>,
+    pkv: &Res<PkvStore>,
+) {
+    append_flex_column_start(parent, |parent| {
+        append_middle_text(
+            parent,
+            title,
+            font_assets,
+            text_colors,
+            None::<NoneComponent>,
+        );
+        append_flex_row_evenly(parent, |parent| {
+            let fire_mode = read_fire_mode(key, pkv);
+            [
+                (FireMode::Single, "По одному"),
+                (FireMode::Rapid, "Очередью"),
+            ]
+            .iter()
+            .for_each(|(v, text)| {
+                let selected = fire_mode == *v;
+                append_middle_text_button(
+                    parent,
+                    Some(FireModeButton {
+                        value: *v,
+                        key: key.to_string(),
+                        pressed: selected,
+                        color_type: ColorType::Green,
+                    }),
+                    &ColorType::Green,
+                    text,
+                    font_assets,
+                    text_colors,
+                    button_colors,
+                    selected,
+                );
+            });
+        });
+    });
+}
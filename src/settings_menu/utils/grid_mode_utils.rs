@@ -0,0 +1,70 @@
+use bevy::prelude::{ChildBuilder, Res};
+use bevy_pkv::PkvStore;
+
+use crate::{
+    gameplay::grid::resources::GridMode,
+    loading::font_assets::FontAssets,
+    settings_menu::components::GridModeButton,
+    ui::{
+        components::NoneComponent,
+        resources::{ColorType, UIMenuButtonColors, UIMenuTextColors},
+        utils::{
+            button_utils::append_middle_text_button,
+            flex_utils::{append_flex_column_start, append_flex_row_evenly},
+            text_utils::append_middle_text,
+        },
+    },
+};
+
+pub fn read_grid_mode(key: &str, pkv: &Res<PkvStore>) -> GridMode {
+    match pkv.get::<String>(key) {
+        Ok(grid_mode) => GridMode::from(grid_mode.as_str()),
+        Err(_) => GridMode::default(),
+    }
+}
+
+pub fn build_grid_mode_line(
+    title: &str,
+    key: &str,
+    parent: &mut ChildBuilder<'_, '_, '_>,
+    font_assets: &Res<FontAssets>,
+    button_colors: &Res<UIMenuButtonColors>,
+    text_colors: &Res<UIMenuTextColors>,
+    pkv: &Res<PkvStore>,
+) {
+    append_flex_column_start(parent, |parent| {
+        append_middle_text(
+            parent,
+            title,
+            font_assets,
+            text_colors,
+            None::<NoneComponent>,
+        );
+        append_flex_row_evenly(parent, |parent| {
+            let grid_mode = read_grid_mode(key, pkv);
+            [
+                (GridMode::Magnetic, "Магниты"),
+                (GridMode::Classic, "Классика"),
+            ]
+            .iter()
+            .for_each(|(v, text)| {
+                let selected = grid_mode == *v;
+                append_middle_text_button(
+                    parent,
+                    Some(GridModeButton {
+                        value: *v,
+                        key: key.to_string(),
+                        pressed: selected,
+                        color_type: ColorType::Green,
+                    }),
+                    &ColorType::Green,
+                    text,
+                    font_assets,
+                    text_colors,
+                    button_colors,
+                    selected,
+                );
+            });
+        });
+    });
+}
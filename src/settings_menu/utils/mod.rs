@@ -1,5 +1,10 @@
 pub mod colors_utils;
 pub mod columns_utils;
+pub mod fire_mode_utils;
+pub mod grid_mode_utils;
 pub mod move_down_utils;
+pub mod pop_hint_utils;
+pub mod queue_depth_utils;
 pub mod rows_utils;
+pub mod seed_utils;
 pub mod volume_utils;
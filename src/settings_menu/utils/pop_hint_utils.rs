@@ -0,0 +1,66 @@
+use bevy::prelude::{ChildBuilder, Res};
+use bevy_pkv::PkvStore;
+
+use crate::{
+    loading::font_assets::FontAssets,
+    settings_menu::components::PopHintButton,
+    ui::{
+        components::NoneComponent,
+        resources::{ColorType, UIMenuButtonColors, UIMenuTextColors},
+        utils::{
+            button_utils::append_middle_text_button,
+            flex_utils::{append_flex_column_start, append_flex_row_evenly},
+            text_utils::append_middle_text,
+        },
+    },
+};
+
+pub fn read_pop_hint(key: &str, pkv: &Res<PkvStore>) -> bool {
+    match pkv.get::<String>(key) {
+        Ok(pop_hint) => pop_hint.parse::<bool>().unwrap_or(false),
+        Err(_) => false,
+    }
+}
+
+pub fn build_pop_hint_line(
+    title: &str,
+    key: &str,
+    parent: &mut ChildBuilder<'_, '_, '_>,
+    font_assets: &Res<FontAssets>,
+    button_colors: &Res<UIMenuButtonColors>,
+    text_colors: &Res<UIMenuTextColors>,
+    pkv: &Res<PkvStore>,
+) {
+    append_flex_column_start(parent, |parent| {
+        append_middle_text(
+            parent,
+            title,
+            font_assets,
+            text_colors,
+            None::<NoneComponent>,
+        );
+        append_flex_row_evenly(parent, |parent| {
+            let pop_hint = read_pop_hint(key, pkv);
+            [(false, "Выкл"), (true, "Вкл")]
+                .iter()
+                .for_each(|(v, text)| {
+                    let selected = pop_hint == *v;
+                    append_middle_text_button(
+                        parent,
+                        Some(PopHintButton {
+                            value: *v,
+                            key: key.to_string(),
+                            pressed: selected,
+                            color_type: ColorType::Green,
+                        }),
+                        &ColorType::Green,
+                        text,
+                        font_assets,
+                        text_colors,
+                        button_colors,
+                        selected,
+                    );
+                });
+        });
+    });
+}
@@ -0,0 +1,71 @@
+use bevy::prelude::{ChildBuilder, Res};
+use bevy_pkv::PkvStore;
+
+use crate::{
+    constants::{DEFAULT_QUEUE_DEPTH, MAX_QUEUE_DEPTH, MIN_QUEUE_DEPTH},
+    loading::font_assets::FontAssets,
+    settings_menu::components::QueueDepthButton,
+    ui::{
+        components::NoneComponent,
+        resources::{ColorType, UIMenuButtonColors, UIMenuTextColors},
+        utils::{
+            button_utils::append_middle_text_button,
+            flex_utils::{append_flex_column_start, append_flex_row_evenly},
+            text_utils::append_middle_text,
+        },
+    },
+};
+
+pub fn read_queue_depth(key: &str, pkv: &Res<PkvStore>) -> u8 {
+    match pkv.get::<String>(key) {
+        Ok(queue_depth) => {
+            if let Ok(parsed) = queue_depth.parse::<u8>() {
+                parsed.clamp(MIN_QUEUE_DEPTH, MAX_QUEUE_DEPTH)
+            } else {
+                DEFAULT_QUEUE_DEPTH
+            }
+        }
+        Err(_) => DEFAULT_QUEUE_DEPTH,
+    }
+}
+
+pub fn build_queue_depth_line(
+    title: &str,
+    key: &str,
+    parent: &mut ChildBuilder<'_, '_, '_>,
+    font_assets: &Res<FontAssets>,
+    button_colors: &Res<UIMenuButtonColors>,
+    text_colors: &Res<UIMenuTextColors>,
+    pkv: &Res<PkvStore>,
+) {
+    append_flex_column_start(parent, |parent| {
+        append_middle_text(
+            parent,
+            title,
+            font_assets,
+            text_colors,
+            None::<NoneComponent>,
+        );
+        append_flex_row_evenly(parent, |parent| {
+            let queue_depth = read_queue_depth(key, pkv);
+            (MIN_QUEUE_DEPTH..=MAX_QUEUE_DEPTH).for_each(|v| {
+                let selected = queue_depth == v;
+                append_middle_text_button(
+                    parent,
+                    Some(QueueDepthButton {
+                        value: v,
+                        key: key.to_string(),
+                        pressed: selected,
+                        color_type: ColorType::Green,
+                    }),
+                    &ColorType::Green,
+                    v.to_string().as_str(),
+                    font_assets,
+                    text_colors,
+                    button_colors,
+                    selected,
+                );
+            });
+        });
+    });
+}
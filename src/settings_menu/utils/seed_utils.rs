@@ -0,0 +1,97 @@
+use bevy::prelude::{ChildBuilder, Res};
+use bevy_pkv::PkvStore;
+
+use crate::{
+    constants::LAST_SEED_KEY,
+    loading::font_assets::FontAssets,
+    settings_menu::components::{SeedButton, SeedText},
+    ui::{
+        components::NoneComponent,
+        resources::{ColorType, UIMenuButtonColors, UIMenuTextColors},
+        utils::{
+            button_utils::append_middle_text_button,
+            flex_utils::{append_flex_column_start, append_flex_row_evenly},
+            text_utils::append_middle_text,
+        },
+    },
+};
+
+pub fn read_seed(key: &str, pkv: &PkvStore) -> Option<u64> {
+    match pkv.get::<String>(key) {
+        Ok(seed) => match seed.parse::<u64>() {
+            // zero means random seed for every game
+            Ok(parsed) if parsed > 0 => Some(parsed),
+            _ => None,
+        },
+        Err(_) => None,
+    }
+}
+
+pub fn format_seed(some_seed: Option<u64>) -> String {
+    match some_seed {
+        Some(seed) => seed.to_string(),
+        None => "случайный".to_string(),
+    }
+}
+
+pub fn build_seed_line(
+    title: &str,
+    key: &str,
+    parent: &mut ChildBuilder<'_, '_, '_>,
+    font_assets: &Res<FontAssets>,
+    button_colors: &Res<UIMenuButtonColors>,
+    text_colors: &Res<UIMenuTextColors>,
+    pkv: &Res<PkvStore>,
+) {
+    append_flex_column_start(parent, |parent| {
+        append_middle_text(
+            parent,
+            title,
+            font_assets,
+            text_colors,
+            None::<NoneComponent>,
+        );
+        append_flex_row_evenly(parent, |parent| {
+            let some_seed = read_seed(key, pkv);
+            append_middle_text(
+                parent,
+                format_seed(some_seed).as_str(),
+                font_assets,
+                text_colors,
+                Some(SeedText {}),
+            );
+            append_middle_text_button(
+                parent,
+                Some(SeedButton {
+                    fixed: false,
+                    key: key.to_string(),
+                    last_key: LAST_SEED_KEY.to_string(),
+                    pressed: some_seed.is_none(),
+                    color_type: ColorType::Green,
+                }),
+                &ColorType::Green,
+                "Случайный",
+                font_assets,
+                text_colors,
+                button_colors,
+                some_seed.is_none(),
+            );
+            append_middle_text_button(
+                parent,
+                Some(SeedButton {
+                    fixed: true,
+                    key: key.to_string(),
+                    last_key: LAST_SEED_KEY.to_string(),
+                    pressed: some_seed.is_some(),
+                    color_type: ColorType::Green,
+                }),
+                &ColorType::Green,
+                "Последний",
+                font_assets,
+                text_colors,
+                button_colors,
+                some_seed.is_some(),
+            );
+        });
+    });
+}
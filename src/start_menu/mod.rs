@@ -6,6 +6,7 @@ use self::systems::keydown_quit_detect;
 use crate::ui::systems::interact_with_quit_button;
 use crate::{
     components::AppState,
+    gameplay::level::systems::interact_with_start_level_button,
     ui::systems::{cleanup_menu, interact_with_next_state_button},
 };
 use bevy::prelude::{in_state, App, IntoSystemConfigs, OnEnter, OnExit, Plugin, Update};
@@ -23,6 +24,7 @@ impl Plugin for StartMenuPlugin {
                 Update,
                 (
                     interact_with_next_state_button,
+                    interact_with_start_level_button,
                     keydown_init_detect,
                     #[cfg(not(target_arch = "wasm32"))]
                     #[allow(dead_code)]
@@ -2,13 +2,15 @@ use bevy::prelude::{Commands, Input, KeyCode, NextState, Res, ResMut};
 #[cfg(not(target_arch = "wasm32"))]
 #[allow(dead_code)]
 use bevy::{app::AppExit, prelude::EventWriter};
+use bevy_pkv::PkvStore;
 
 #[cfg(not(target_arch = "wasm32"))]
 #[allow(dead_code)]
 use crate::ui::utils::button_utils::build_quit_button;
 use crate::{
     components::AppState,
-    loading::font_assets::FontAssets,
+    gameplay::level::{components::StartLevelButton, utils::find_campaign_level},
+    loading::{font_assets::FontAssets, level_assets::LevelAssets},
     ui::{
         components::{NextStateButton, NoneComponent},
         resources::{ColorType, UIMenuButtonColors, UIMenuTextColors},
@@ -26,6 +28,8 @@ pub fn setup_menu(
     font_assets: Res<FontAssets>,
     button_colors: Res<UIMenuButtonColors>,
     text_colors: Res<UIMenuTextColors>,
+    level_assets: Res<LevelAssets>,
+    pkv: Res<PkvStore>,
 ) {
     build_ui_camera(&mut commands);
     build_menu(&mut commands, |parent| {
@@ -38,9 +42,9 @@ pub fn setup_menu(
         );
         append_large_text_button(
             parent,
-            Some(NextStateButton {
+            Some(StartLevelButton {
                 color_type: ColorType::Green,
-                next_state: AppState::GameplayInit,
+                some_level: None,
             }),
             &ColorType::Green,
             "Играть",
@@ -49,6 +53,35 @@ pub fn setup_menu(
             &button_colors,
             false,
         );
+        if !level_assets.levels.is_empty() {
+            let campaign_level = find_campaign_level(&pkv, level_assets.levels.len());
+            append_middle_text_button(
+                parent,
+                Some(StartLevelButton {
+                    color_type: ColorType::Blue,
+                    some_level: Some(campaign_level),
+                }),
+                &ColorType::Blue,
+                format!("Кампания: уровень {}", campaign_level + 1).as_str(),
+                &font_assets,
+                &text_colors,
+                &button_colors,
+                false,
+            );
+        }
+        append_middle_text_button(
+            parent,
+            Some(NextStateButton {
+                color_type: ColorType::Blue,
+                next_state: AppState::LevelSelect,
+            }),
+            &ColorType::Blue,
+            "Уровни",
+            &font_assets,
+            &text_colors,
+            &button_colors,
+            false,
+        );
         append_middle_text_button(
             parent,
             Some(NextStateButton {
@@ -0,0 +1,17 @@
+use std::str::FromStr;
+
+/// value which follows `name` in command line
+pub fn read_arg_value(name: &str) -> Option<String> {
+    let mut args = std::env::args();
+    while let Some(arg) = args.next() {
+        if arg == name {
+            return args.next();
+        }
+    }
+    None
+}
+
+/// parsed value which follows `name` in command line, None when it is missing or malformed
+pub fn read_arg<T: FromStr>(name: &str) -> Option<T> {
+    read_arg_value(name).and_then(|value| value.parse::<T>().ok())
+}